@@ -0,0 +1,208 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::ops::RangeInclusive;
+
+/// Keeps chromosomes valid; applied to each offspring after crossover and
+/// after mutation.
+pub trait Constraint<G = f32> {
+    fn repair(&self, chromosome: &mut Chromosome<G>);
+}
+
+/// How a gene that has left its range gets brought back.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Repair {
+    /// Moves the gene to the nearest bound.
+    #[default]
+    Clamp,
+
+    /// Mirrors the gene off the bound it has crossed, as if it bounced.
+    Reflect,
+
+    /// Moves the gene around to the opposite side of the range, as if the
+    /// range was periodic.
+    Wrap,
+}
+
+/// Gene that can be kept within a range.
+pub trait BoundedGene: Copy + PartialOrd {
+    fn repair(self, range: &RangeInclusive<Self>, repair: Repair) -> Self;
+}
+
+impl BoundedGene for f32 {
+    fn repair(self, range: &RangeInclusive<Self>, repair: Repair) -> Self {
+        let (min, max) = (*range.start(), *range.end());
+        let width = max - min;
+
+        if range.contains(&self) || self.is_nan() {
+            return self;
+        }
+
+        if width <= 0.0 {
+            return min;
+        }
+
+        match repair {
+            Repair::Clamp => self.clamp(min, max),
+
+            Repair::Reflect => {
+                let offset = (self - min).rem_euclid(2.0 * width);
+
+                if offset > width {
+                    min + 2.0 * width - offset
+                } else {
+                    min + offset
+                }
+            }
+
+            Repair::Wrap => min + (self - min).rem_euclid(width),
+        }
+    }
+}
+
+impl BoundedGene for i32 {
+    fn repair(self, range: &RangeInclusive<Self>, repair: Repair) -> Self {
+        if range.contains(&self) {
+            return self;
+        }
+
+        // Widths of wide ranges (and twice as much, for reflecting) don't
+        // fit in an `i32`; the repaired gene always does
+        let (min, max) = (*range.start() as i64, *range.end() as i64);
+        let value = self as i64;
+        let width = max - min;
+
+        if width <= 0 {
+            return min as i32;
+        }
+
+        let repaired = match repair {
+            Repair::Clamp => value.clamp(min, max),
+
+            Repair::Reflect => {
+                let offset = (value - min).rem_euclid(2 * width);
+
+                if offset > width {
+                    min + 2 * width - offset
+                } else {
+                    min + offset
+                }
+            }
+
+            Repair::Wrap => min + (value - min).rem_euclid(width + 1),
+        };
+
+        repaired as i32
+    }
+}
+
+/// Keeps each gene within a range - either the same one for all genes, or a
+/// separate one for each gene.
+#[derive(Clone, Debug)]
+pub struct Bounds<G = f32> {
+    ranges: Ranges<G>,
+    repair: Repair,
+}
+
+#[derive(Clone, Debug)]
+enum Ranges<G> {
+    Uniform(RangeInclusive<G>),
+    PerGene(Vec<RangeInclusive<G>>),
+}
+
+impl<G> Bounds<G>
+    where
+        G: BoundedGene,
+{
+    pub fn uniform(range: RangeInclusive<G>, repair: Repair) -> Self {
+        Self {
+            ranges: Ranges::Uniform(range),
+            repair,
+        }
+    }
+
+    /// Creates bounds with a range for each gene; chromosomes repaired with
+    /// them must have exactly that many genes.
+    pub fn per_gene(ranges: Vec<RangeInclusive<G>>, repair: Repair) -> Self {
+        Self {
+            ranges: Ranges::PerGene(ranges),
+            repair,
+        }
+    }
+
+    pub fn range(&self, gene: usize) -> &RangeInclusive<G> {
+        match &self.ranges {
+            Ranges::Uniform(range) => range,
+            Ranges::PerGene(ranges) => &ranges[gene],
+        }
+    }
+}
+
+impl<G> Constraint<G> for Bounds<G>
+    where
+        G: BoundedGene,
+{
+    fn repair(&self, chromosome: &mut Chromosome<G>) {
+        if let Ranges::PerGene(ranges) = &self.ranges {
+            assert_eq!(ranges.len(), chromosome.len());
+        }
+
+        for (idx, gene) in chromosome.iter_mut().enumerate() {
+            *gene = gene.repair(self.range(idx), self.repair);
+        }
+    }
+}
+
+impl<G, C> Constraint<G> for Box<C>
+    where
+        C: Constraint<G> + ?Sized,
+{
+    fn repair(&self, chromosome: &mut Chromosome<G>) {
+        (**self).repair(chromosome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn repair(repair: Repair) -> Vec<f32> {
+        let mut chromosome = vec![-3.5, -1.5, 0.0, 1.0, 1.25, 4.5].into_iter().collect();
+
+        Bounds::uniform(-1.0..=1.0, repair).repair(&mut chromosome);
+
+        chromosome.iter().collect()
+    }
+
+    #[test]
+    fn clamp() {
+        assert_eq!(repair(Repair::Clamp), vec![-1.0, -1.0, 0.0, 1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn reflect() {
+        assert_eq!(repair(Repair::Reflect), vec![0.5, -0.5, 0.0, 1.0, 0.75, 0.5]);
+    }
+
+    #[test]
+    fn wrap() {
+        assert_eq!(repair(Repair::Wrap), vec![0.5, 0.5, 0.0, 1.0, -0.75, 0.5]);
+    }
+
+    #[test]
+    fn per_gene() {
+        let mut chromosome: Chromosome<i32> = vec![-5, 5, 12].into_iter().collect();
+
+        Bounds::per_gene(vec![0..=3, 0..=3, 0..=9], Repair::Wrap).repair(&mut chromosome);
+
+        assert_eq!(chromosome.iter().collect::<Vec<_>>(), vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn wide_range() {
+        let range = (i32::MIN + 10)..=(i32::MAX - 10);
+
+        assert_eq!(i32::MAX.repair(&range, Repair::Clamp), i32::MAX - 10);
+        assert_eq!(i32::MAX.repair(&range, Repair::Reflect), i32::MAX - 20);
+        assert_eq!(i32::MAX.repair(&range, Repair::Wrap), i32::MIN + 19);
+    }
+}
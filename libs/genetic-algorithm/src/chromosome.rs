@@ -1,12 +1,22 @@
+use serde::{Deserialize, Serialize};
 use std::iter::FromIterator;
 use std::ops::Index;
 
-#[derive(Clone, Debug)]
-pub struct Chromosome {
-    genes: Vec<f32>,
+/// Sequence of genes; `G` is `f32` for real-valued problems (e.g. neural
+/// network weights), but it can be any `Copy` type, such as `bool` or `i32`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Chromosome<G = f32> {
+    genes: Vec<G>,
+    step_sizes: Vec<f32>,
 }
 
-impl Chromosome {
+impl<G> Chromosome<G> {
+    /// Attaches mutation step sizes (see `SelfAdaptiveMutation`).
+    pub fn with_step_sizes(mut self, step_sizes: Vec<f32>) -> Self {
+        self.step_sizes = step_sizes;
+        self
+    }
+
     pub fn len(&self) -> usize {
         self.genes.len()
     }
@@ -15,30 +25,111 @@ impl Chromosome {
         self.len() == 0
     }
 
-    pub fn iter(&self) -> impl Iterator<Item = f32> + '_ {
+    pub fn iter(&self) -> impl Iterator<Item = G> + '_
+        where
+            G: Copy,
+    {
         self.genes.iter().copied()
     }
 
-    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut f32> {
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut G> {
         self.genes.iter_mut()
     }
+
+    /// Returns mutation step sizes carried by this chromosome; empty unless
+    /// the chromosome has gone through `SelfAdaptiveMutation`.
+    pub fn step_sizes(&self) -> &[f32] {
+        &self.step_sizes
+    }
+
+    pub fn step_sizes_mut(&mut self) -> &mut Vec<f32> {
+        &mut self.step_sizes
+    }
+
+    /// Gives a freshly crossed-over child the step sizes of its parents,
+    /// averaging them when both parents carry the same number of them.
+    pub(crate) fn inherit_step_sizes(&mut self, parent_a: &Self, parent_b: &Self) {
+        self.step_sizes = if parent_a.step_sizes.len() == parent_b.step_sizes.len() {
+            parent_a
+                .step_sizes
+                .iter()
+                .zip(&parent_b.step_sizes)
+                .map(|(a, b)| (a + b) / 2.0)
+                .collect()
+        } else {
+            parent_a.step_sizes.clone()
+        };
+    }
 }
 
-impl Index<usize> for Chromosome {
-    type Output = f32;
+impl<G> Chromosome<G>
+    where
+        G: GeneDistance,
+{
+    /// Returns the average distance between genes at the same locus; used to
+    /// tell species apart (see `GeneticAlgorithm::with_speciation()`).
+    pub fn distance(&self, other: &Self) -> f32 {
+        assert_eq!(self.len(), other.len());
+
+        if self.is_empty() {
+            return 0.0;
+        }
+
+        let sum: f32 = self
+            .genes
+            .iter()
+            .zip(&other.genes)
+            .map(|(a, b)| a.distance(b))
+            .sum();
+
+        sum / (self.len() as f32)
+    }
+}
+
+impl<G> Index<usize> for Chromosome<G> {
+    type Output = G;
 
     fn index(&self, index: usize) -> &Self::Output {
         &self.genes[index]
     }
 }
 
-impl FromIterator<f32> for Chromosome {
+impl<G> FromIterator<G> for Chromosome<G> {
     fn from_iter<T>(iter: T) -> Self
         where
-            T: IntoIterator<Item = f32>,
+            T: IntoIterator<Item = G>,
     {
         Self {
             genes: iter.into_iter().collect(),
+            step_sizes: Vec::new(),
+        }
+    }
+}
+
+/// Gene that can be compared with another one, so that chromosomes can be
+/// clustered into species.
+pub trait GeneDistance {
+    fn distance(&self, other: &Self) -> f32;
+}
+
+impl GeneDistance for f32 {
+    fn distance(&self, other: &Self) -> f32 {
+        (self - other).abs()
+    }
+}
+
+impl GeneDistance for i32 {
+    fn distance(&self, other: &Self) -> f32 {
+        self.abs_diff(*other) as f32
+    }
+}
+
+impl GeneDistance for bool {
+    fn distance(&self, other: &Self) -> f32 {
+        if self == other {
+            0.0
+        } else {
+            1.0
         }
     }
 }
@@ -57,6 +148,7 @@ mod tests {
     fn chromosome() -> Chromosome {
         Chromosome {
             genes: vec![3.0, 1.0, 2.0],
+            step_sizes: Vec::new(),
         }
     }
 
@@ -109,4 +201,36 @@ mod tests {
         assert_eq!(chromosome[1], 1.0);
         assert_eq!(chromosome[2], 2.0);
     }
+
+    #[test]
+    fn discrete_genes() {
+        let mut chromosome: Chromosome<bool> = vec![true, false, true].into_iter().collect();
+
+        chromosome.iter_mut().for_each(|gene| {
+            *gene = !*gene;
+        });
+
+        let genes: Vec<_> = chromosome.iter().collect();
+
+        assert_eq!(genes, vec![false, true, false]);
+    }
+
+    #[test]
+    fn distance() {
+        let other: Chromosome = vec![1.0, 1.0, 5.0].into_iter().collect();
+
+        approx::assert_relative_eq!(chromosome().distance(&chromosome()), 0.0);
+        approx::assert_relative_eq!(chromosome().distance(&other), (2.0 + 0.0 + 3.0) / 3.0);
+    }
+
+    #[test]
+    fn inherit_step_sizes() {
+        let parent_a = chromosome().with_step_sizes(vec![0.1, 0.2]);
+        let parent_b = chromosome().with_step_sizes(vec![0.3, 0.4]);
+        let mut child = chromosome();
+
+        child.inherit_step_sizes(&parent_a, &parent_b);
+
+        approx::assert_relative_eq!(child.step_sizes(), [0.2, 0.3].as_slice());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,428 @@
+use crate::*;
+use nalgebra::{DMatrix, DVector, SymmetricEigen};
+use rand_distr::StandardNormal;
+use std::cmp::Ordering;
+
+/// Largest ratio between the longest and the shortest axis of the search
+/// distribution (squared) before it's considered degenerate.
+const MAX_CONDITION: f32 = 1e7;
+
+/// Step size, relative to the initial one, below which the search is
+/// considered converged.
+const MIN_RELATIVE_STEP: f32 = 1e-5;
+
+/// Covariance Matrix Adaptation Evolution Strategy with IPOP restarts.
+///
+/// Instead of recombining individuals, CMA-ES samples each generation from a
+/// multivariate normal distribution, whose mean, covariance matrix and step
+/// size (sigma) get adapted towards the fittest samples. When the search
+/// converges or stagnates, it restarts with a twice as large population,
+/// from a random point around where the first search started.
+///
+/// The state is initialized from the first population passed to `evolve()`,
+/// whose size becomes the initial number of samples per generation.
+pub struct CmaEs {
+    sigma: f32,
+    max_restarts: usize,
+    restarts: usize,
+    constraint: Option<Box<dyn Constraint>>,
+    state: Option<CmaEsState>,
+
+    /// Mean of the first population, around which restarts begin.
+    initial_mean: Option<DVector<f32>>,
+}
+
+impl CmaEs {
+    pub fn new(sigma: f32) -> Self {
+        Self::try_new(sigma).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(sigma: f32) -> Result<Self, GaError> {
+        GaError::check_positive("sigma", sigma)?;
+
+        Ok(Self {
+            sigma,
+            max_restarts: 4,
+            restarts: 0,
+            constraint: None,
+            state: None,
+            initial_mean: None,
+        })
+    }
+
+    /// Limits how many times the population can get doubled (4 by default).
+    pub fn with_max_restarts(mut self, max_restarts: usize) -> Self {
+        self.max_restarts = max_restarts;
+        self
+    }
+
+    /// Repairs each sample (e.g. keeps its genes within `Bounds`); the
+    /// distribution gets updated from the repaired samples.
+    pub fn with_constraint(mut self, constraint: impl Constraint + 'static) -> Self {
+        self.constraint = Some(Box::new(constraint));
+        self
+    }
+
+    pub fn restarts(&self) -> usize {
+        self.restarts
+    }
+
+    /// Returns the current step size, if the state has been initialized.
+    pub fn sigma(&self) -> Option<f32> {
+        self.state.as_ref().map(|state| state.sigma)
+    }
+
+    /// Updates the distribution with given evaluated samples and draws the
+    /// next generation from it.
+    pub fn evolve<I>(&mut self, rng: &mut dyn RngCore, population: &[I]) -> Vec<I>
+        where
+            I: Individual,
+    {
+        assert!(!population.is_empty());
+
+        let mut population: Vec<_> = population.iter().collect();
+
+        // Fittest first
+        population.sort_by(|a, b| {
+            b.fitness()
+                .partial_cmp(&a.fitness())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let best_fitness = population[0].fitness();
+        let ranked: Vec<_> = population.iter().map(|i| i.chromosome()).collect();
+
+        match &mut self.state {
+            Some(state) => {
+                state.update(&ranked, best_fitness);
+
+                if state.should_restart() && self.restarts < self.max_restarts {
+                    self.restarts += 1;
+
+                    // Starting over from where the search has converged would
+                    // most likely find the same optimum again
+                    let initial_mean = self.initial_mean.as_ref().unwrap();
+
+                    let mean = initial_mean
+                        + DVector::from_fn(initial_mean.len(), |_, _| {
+                            self.sigma * rng.sample::<f32, _>(StandardNormal)
+                        });
+
+                    *state = CmaEsState::new(mean, self.sigma, state.lambda * 2);
+                }
+            }
+
+            None => {
+                let dims = ranked[0].len();
+                let mut state = CmaEsState::new(DVector::zeros(dims), self.sigma, population.len());
+
+                state.mean = state.recombine(&ranked);
+                self.initial_mean = Some(state.mean.clone());
+                self.state = Some(state);
+            }
+        }
+
+        self.state
+            .as_ref()
+            .unwrap()
+            .sample(rng)
+            .into_iter()
+            .map(|mut chromosome| {
+                if let Some(constraint) = &self.constraint {
+                    constraint.repair(&mut chromosome);
+                }
+
+                I::create(chromosome)
+            })
+            .collect()
+    }
+}
+
+struct CmaEsState {
+    lambda: usize,
+    weights: Vec<f32>,
+    mu_eff: f32,
+    cc: f32,
+    cs: f32,
+    c1: f32,
+    c_mu: f32,
+    damps: f32,
+    chi_n: f32,
+
+    mean: DVector<f32>,
+    sigma: f32,
+    initial_sigma: f32,
+    cov: DMatrix<f32>,
+    b: DMatrix<f32>,
+    d: DVector<f32>,
+    pc: DVector<f32>,
+    ps: DVector<f32>,
+
+    generation: usize,
+    best_fitness: f32,
+    stagnation: usize,
+}
+
+impl CmaEsState {
+    fn new(mean: DVector<f32>, sigma: f32, lambda: usize) -> Self {
+        assert!(lambda >= 2);
+
+        let dims = mean.len();
+        let n = dims as f32;
+        let mu = lambda / 2;
+
+        let weights: Vec<_> = (1..=mu)
+            .map(|i| ((mu as f32) + 0.5).ln() - (i as f32).ln())
+            .collect();
+
+        let sum: f32 = weights.iter().sum();
+        let weights: Vec<_> = weights.iter().map(|w| w / sum).collect();
+        let mu_eff = 1.0 / weights.iter().map(|w| w * w).sum::<f32>();
+
+        let cc = (4.0 + mu_eff / n) / (n + 4.0 + 2.0 * mu_eff / n);
+        let cs = (mu_eff + 2.0) / (n + mu_eff + 5.0);
+        let c1 = 2.0 / ((n + 1.3).powi(2) + mu_eff);
+
+        let c_mu = (1.0 - c1)
+            .min(2.0 * (mu_eff - 2.0 + 1.0 / mu_eff) / ((n + 2.0).powi(2) + mu_eff));
+
+        let damps = 1.0 + 2.0 * (((mu_eff - 1.0) / (n + 1.0)).sqrt() - 1.0).max(0.0) + cs;
+        let chi_n = n.sqrt() * (1.0 - 1.0 / (4.0 * n) + 1.0 / (21.0 * n * n));
+
+        Self {
+            lambda,
+            weights,
+            mu_eff,
+            cc,
+            cs,
+            c1,
+            c_mu,
+            damps,
+            chi_n,
+            mean,
+            sigma,
+            initial_sigma: sigma,
+            cov: DMatrix::identity(dims, dims),
+            b: DMatrix::identity(dims, dims),
+            d: DVector::from_element(dims, 1.0),
+            pc: DVector::zeros(dims),
+            ps: DVector::zeros(dims),
+            generation: 0,
+            best_fitness: f32::NEG_INFINITY,
+            stagnation: 0,
+        }
+    }
+
+    /// Returns the weighted mean of the best `mu` chromosomes.
+    fn recombine(&self, ranked: &[&Chromosome]) -> DVector<f32> {
+        assert!(ranked.len() >= self.weights.len());
+
+        ranked
+            .iter()
+            .zip(&self.weights)
+            .fold(DVector::zeros(self.mean.len()), |mean, (chromosome, weight)| {
+                mean + Self::vector(chromosome) * *weight
+            })
+    }
+
+    fn update(&mut self, ranked: &[&Chromosome], best_fitness: f32) {
+        let n = self.mean.len() as f32;
+        let old_mean = self.mean.clone();
+
+        self.mean = self.recombine(ranked);
+        self.generation += 1;
+
+        let y_w = (&self.mean - &old_mean) / self.sigma;
+
+        let c_inv_sqrt = &self.b
+            * DMatrix::from_diagonal(&self.d.map(|d| 1.0 / d))
+            * self.b.transpose();
+
+        self.ps = &self.ps * (1.0 - self.cs)
+            + c_inv_sqrt * &y_w * (self.cs * (2.0 - self.cs) * self.mu_eff).sqrt();
+
+        let hsig = self.ps.norm()
+            / (1.0 - (1.0 - self.cs).powi(2 * self.generation as i32)).sqrt()
+            / self.chi_n
+            < 1.4 + 2.0 / (n + 1.0);
+
+        let hsig = if hsig { 1.0 } else { 0.0 };
+
+        self.pc = &self.pc * (1.0 - self.cc)
+            + &y_w * (hsig * (self.cc * (2.0 - self.cc) * self.mu_eff).sqrt());
+
+        let rank_mu = ranked.iter().zip(&self.weights).fold(
+            DMatrix::zeros(self.mean.len(), self.mean.len()),
+            |rank_mu, (chromosome, weight)| {
+                let y = (Self::vector(chromosome) - &old_mean) / self.sigma;
+
+                rank_mu + &y * y.transpose() * *weight
+            },
+        );
+
+        let rank_one =
+            &self.pc * self.pc.transpose() + &self.cov * ((1.0 - hsig) * self.cc * (2.0 - self.cc));
+
+        self.cov = &self.cov * (1.0 - self.c1 - self.c_mu) + rank_one * self.c1 + rank_mu * self.c_mu;
+        self.sigma *= ((self.cs / self.damps) * (self.ps.norm() / self.chi_n - 1.0)).exp();
+
+        // Keep the matrix exactly symmetric, so that it can be decomposed
+        self.cov = (&self.cov + self.cov.transpose()) * 0.5;
+
+        let eigen = SymmetricEigen::new(self.cov.clone());
+
+        self.b = eigen.eigenvectors;
+        self.d = eigen.eigenvalues.map(|value| value.max(f32::EPSILON).sqrt());
+
+        if best_fitness > self.best_fitness {
+            self.best_fitness = best_fitness;
+            self.stagnation = 0;
+        } else {
+            self.stagnation += 1;
+        }
+    }
+
+    fn should_restart(&self) -> bool {
+        let n = self.mean.len() as f32;
+        let max_d = self.d.max();
+        let min_d = self.d.min();
+
+        let converged = self.sigma * max_d < MIN_RELATIVE_STEP * self.initial_sigma;
+        let degenerate = (max_d / min_d).powi(2) > MAX_CONDITION;
+        let stagnated = (self.stagnation as f32) > 10.0 + (30.0 * n / self.lambda as f32).ceil();
+
+        converged || degenerate || stagnated
+    }
+
+    fn sample(&self, rng: &mut dyn RngCore) -> Vec<Chromosome> {
+        (0..self.lambda)
+            .map(|_| {
+                let z = DVector::from_fn(self.mean.len(), |_, _| rng.sample(StandardNormal));
+                let x = &self.mean + &self.b * self.d.component_mul(&z) * self.sigma;
+
+                x.iter().copied().collect()
+            })
+            .collect()
+    }
+
+    fn vector(chromosome: &Chromosome) -> DVector<f32> {
+        DVector::from_iterator(chromosome.len(), chromosome.iter())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Gets fitter as its genes approach `[1.0, 2.0, 3.0, ...]`.
+    struct SphereIndividual(Chromosome);
+
+    impl Individual for SphereIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self(chromosome)
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.0
+        }
+
+        fn fitness(&self) -> f32 {
+            -self
+                .0
+                .iter()
+                .enumerate()
+                .map(|(idx, gene)| (gene - (idx + 1) as f32).powi(2))
+                .sum::<f32>()
+        }
+    }
+
+    /// Every individual is as good as any other one.
+    struct FlatIndividual(Chromosome);
+
+    impl Individual for FlatIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self(chromosome)
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.0
+        }
+
+        fn fitness(&self) -> f32 {
+            0.0
+        }
+    }
+
+    fn population<I>(rng: &mut dyn RngCore) -> Vec<I>
+        where
+            I: Individual,
+    {
+        (0..10)
+            .map(|_| I::create((0..5).map(|_| rng.gen_range(-1.0..1.0)).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn converges_on_sphere() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut cma_es = CmaEs::new(0.5).with_max_restarts(0);
+        let mut population: Vec<SphereIndividual> = population(&mut rng);
+
+        for _ in 0..200 {
+            population = cma_es.evolve(&mut rng, &population);
+        }
+
+        let best = population
+            .iter()
+            .map(|i| i.fitness())
+            .fold(f32::NEG_INFINITY, f32::max);
+
+        assert!(best > -1e-3, "best fitness = {}", best);
+        assert!(cma_es.sigma().unwrap() < 0.5);
+    }
+
+    #[test]
+    fn restarts_with_larger_population() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut cma_es = CmaEs::new(0.5).with_max_restarts(2);
+        let mut population: Vec<FlatIndividual> = population(&mut rng);
+
+        for _ in 0..200 {
+            population = cma_es.evolve(&mut rng, &population);
+        }
+
+        assert_eq!(cma_es.restarts(), 2);
+        assert_eq!(population.len(), 40);
+    }
+
+    #[test]
+    fn restarts_around_initial_mean() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut cma_es = CmaEs::new(0.5).with_max_restarts(1);
+        let population: Vec<SphereIndividual> = population(&mut rng);
+
+        cma_es.evolve(&mut rng, &population);
+
+        // Pretend the search has got stuck far away from where it started
+        let state = cma_es.state.as_mut().unwrap();
+
+        state.best_fitness = f32::INFINITY;
+        state.stagnation = 1000;
+
+        let stuck: Vec<_> = population
+            .iter()
+            .map(|i| SphereIndividual(i.0.iter().map(|gene| gene + 100.0).collect()))
+            .collect();
+
+        let population = cma_es.evolve(&mut rng, &stuck);
+        let mean = &cma_es.state.as_ref().unwrap().mean;
+        let initial_mean = cma_es.initial_mean.as_ref().unwrap();
+
+        assert_eq!(cma_es.restarts(), 1);
+        assert_eq!(population.len(), 20);
+        assert!((mean - initial_mean).norm() < 5.0 * 0.5 * 5.0_f32.sqrt());
+        assert_ne!(mean, initial_mean);
+    }
+}
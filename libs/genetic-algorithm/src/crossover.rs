@@ -1,13 +1,61 @@
+mod arithmetic;
+mod blend;
+mod k_point;
+mod segment;
+mod simulated_binary;
+mod single_point;
 mod uniform;
 
+pub use self::arithmetic::*;
+pub use self::blend::*;
+pub use self::k_point::*;
+pub use self::segment::*;
+pub use self::simulated_binary::*;
+pub use self::single_point::*;
 pub use self::uniform::*;
 use crate::*;
 
-pub trait CrossoverMethod {
+pub trait CrossoverMethod<G = f32> {
     fn crossover(
         &self,
         rng: &mut dyn RngCore,
-        parent_a: &Chromosome,
-        parent_b: &Chromosome,
-    ) -> Chromosome;
-}
\ No newline at end of file
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G>;
+
+    /// Like `crossover()`, but returns an error instead of panicking when the
+    /// parents don't fit together.
+    fn try_crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Result<Chromosome<G>, GaError> {
+        GaError::check_length(parent_a.len(), parent_b.len())?;
+
+        Ok(self.crossover(rng, parent_a, parent_b))
+    }
+}
+
+impl<G, C> CrossoverMethod<G> for Box<C>
+    where
+        C: CrossoverMethod<G> + ?Sized,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        (**self).crossover(rng, parent_a, parent_b)
+    }
+
+    fn try_crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Result<Chromosome<G>, GaError> {
+        (**self).try_crossover(rng, parent_a, parent_b)
+    }
+}
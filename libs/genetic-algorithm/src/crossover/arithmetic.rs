@@ -0,0 +1,60 @@
+use crate::*;
+
+/// Creates a child whose genes are `alpha * a + (1 - alpha) * b`.
+#[derive(Clone, Debug)]
+pub struct ArithmeticCrossover {
+    alpha: f32,
+}
+
+impl ArithmeticCrossover {
+    pub fn new(alpha: f32) -> Self {
+        Self::try_new(alpha).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(alpha: f32) -> Result<Self, GaError> {
+        GaError::check(
+            (0.0..=1.0).contains(&alpha),
+            "alpha",
+            alpha,
+            "a number within 0.0..=1.0",
+        )?;
+
+        Ok(Self { alpha })
+    }
+}
+
+impl CrossoverMethod for ArithmeticCrossover {
+    fn crossover(
+        &self,
+        _rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Chromosome {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(a, b)| self.alpha * a + (1.0 - self.alpha) * b)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome = vec![1.0, 2.0, 3.0, 4.0].into_iter().collect();
+        let parent_b: Chromosome = vec![-1.0, 0.0, 5.0, 4.0].into_iter().collect();
+
+        let child = ArithmeticCrossover::new(0.25).crossover(&mut rng, &parent_a, &parent_b);
+        let expected: Chromosome = vec![-0.5, 0.5, 4.5, 4.0].into_iter().collect();
+
+        assert_eq!(child, expected);
+    }
+}
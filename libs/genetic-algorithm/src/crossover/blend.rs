@@ -0,0 +1,68 @@
+use crate::*;
+
+/// BLX-alpha: each gene is drawn uniformly from the range spanned by both
+/// parents, extended on each side by `alpha` times its width.
+#[derive(Clone, Debug)]
+pub struct BlendCrossover {
+    alpha: f32,
+}
+
+impl BlendCrossover {
+    pub fn new(alpha: f32) -> Self {
+        Self::try_new(alpha).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(alpha: f32) -> Result<Self, GaError> {
+        GaError::check_non_negative("alpha", alpha)?;
+
+        Ok(Self { alpha })
+    }
+}
+
+impl CrossoverMethod for BlendCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Chromosome {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(a, b)| {
+                let extent = self.alpha * (a - b).abs();
+                let min = a.min(b) - extent;
+                let max = a.max(b) + extent;
+
+                min + (max - min) * rng.gen::<f32>()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome = vec![1.0, 2.0, 3.0, 4.0].into_iter().collect();
+        let parent_b: Chromosome = vec![-1.0, 0.0, 5.0, 4.0].into_iter().collect();
+
+        let child = BlendCrossover::new(0.5).crossover(&mut rng, &parent_a, &parent_b);
+        let expected: Chromosome = vec![-1.2510376, 2.347679, 5.6362524, 4.0].into_iter().collect();
+
+        assert_eq!(child, expected);
+
+        // Each gene lies within the parents' range, extended by 50% on both
+        // sides
+        assert!((-2.0..=2.0).contains(&child[0]));
+        assert!((-1.0..=3.0).contains(&child[1]));
+        assert!((2.0..=6.0).contains(&child[2]));
+    }
+}
@@ -0,0 +1,98 @@
+use crate::*;
+use rand::seq::index;
+
+#[derive(Clone, Debug)]
+pub struct KPointCrossover {
+    points: usize,
+}
+
+impl KPointCrossover {
+    pub fn new(points: usize) -> Self {
+        Self::try_new(points).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(points: usize) -> Result<Self, GaError> {
+        GaError::check_positive("points", points as f32)?;
+
+        Ok(Self { points })
+    }
+}
+
+impl<G> CrossoverMethod<G> for KPointCrossover
+    where
+        G: Copy,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        if parent_a.len() < 2 {
+            return parent_a.clone();
+        }
+
+        // Cut points are drawn from `1..len`, so that every segment is
+        // non-empty
+        let points = self.points.min(parent_a.len() - 1);
+        let mut points = index::sample(rng, parent_a.len() - 1, points).into_vec();
+
+        points.sort_unstable();
+
+        let mut points = points.into_iter().map(|point| point + 1).peekable();
+        let mut from_a = true;
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .enumerate()
+            .map(|(idx, (a, b))| {
+                if points.next_if_eq(&idx).is_some() {
+                    from_a = !from_a;
+                }
+
+                if from_a {
+                    a
+                } else {
+                    b
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome = (1..=100).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=100).map(|n| -n as f32).collect();
+
+        let child = KPointCrossover::new(3).crossover(&mut rng, &parent_a, &parent_b);
+        let genes: Vec<_> = child.iter().collect();
+
+        // Indices at which the child switches from one parent to the other
+        let points: Vec<_> = genes
+            .windows(2)
+            .enumerate()
+            .filter(|(_, w)| w[0].signum() != w[1].signum())
+            .map(|(idx, _)| idx + 1)
+            .collect();
+
+        assert_eq!(points, vec![19, 63, 83]);
+        assert!(genes[0] > 0.0);
+
+        // Every gene is taken from either of the parents, at the same locus
+        assert!(genes
+            .iter()
+            .enumerate()
+            .all(|(idx, &gene)| gene == parent_a[idx] || gene == parent_b[idx]));
+    }
+}
@@ -0,0 +1,120 @@
+use crate::*;
+
+/// Treats the chromosome as a sequence of segments (e.g. all the weights of a
+/// single neuron) and copies each segment as a whole from either of the
+/// parents, so that building blocks spanning several genes don't get torn
+/// apart.
+#[derive(Clone, Debug)]
+pub struct SegmentCrossover {
+    layout: Vec<usize>,
+}
+
+impl SegmentCrossover {
+    /// Creates a crossover for given segment lengths, in the order they appear
+    /// in the chromosome.
+    pub fn new(layout: Vec<usize>) -> Self {
+        Self::try_new(layout).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(layout: Vec<usize>) -> Result<Self, GaError> {
+        if let Some(&len) = layout.iter().find(|&&len| len == 0) {
+            return Err(GaError::InvalidParameter {
+                name: "segment length",
+                value: len as f32,
+                expected: "a positive number",
+            });
+        }
+
+        Ok(Self { layout })
+    }
+}
+
+impl<G> CrossoverMethod<G> for SegmentCrossover
+    where
+        G: Copy,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        self.try_crossover(rng, parent_a, parent_b)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    fn try_crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Result<Chromosome<G>, GaError> {
+        GaError::check_length(parent_a.len(), parent_b.len())?;
+        GaError::check_length(self.layout.iter().sum(), parent_a.len())?;
+
+        let mut genes = parent_a.iter().zip(parent_b.iter());
+
+        Ok(self
+            .layout
+            .iter()
+            .flat_map(|&len| {
+                let from_a = rng.gen_bool(0.5);
+
+                genes
+                    .by_ref()
+                    .take(len)
+                    .map(move |(a, b)| if from_a { a } else { b })
+                    .collect::<Vec<_>>()
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome = (1..=100).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=100).map(|n| -n as f32).collect();
+        let layout = vec![10; 10];
+
+        let child = SegmentCrossover::new(layout).crossover(&mut rng, &parent_a, &parent_b);
+        let genes: Vec<_> = child.iter().collect();
+
+        // Every segment has been taken as a whole from one of the parents
+        for segment in genes.chunks(10) {
+            assert!(segment
+                .iter()
+                .all(|gene| gene.signum() == segment[0].signum()));
+        }
+
+        // Every gene is taken from either of the parents, at the same locus
+        assert!(genes
+            .iter()
+            .enumerate()
+            .all(|(idx, &gene)| gene == parent_a[idx] || gene == parent_b[idx]));
+
+        // Segments taken from `parent_a`
+        let from_a: Vec<_> = genes.chunks(10).map(|segment| segment[0] > 0.0).collect();
+
+        assert_eq!(
+            from_a,
+            vec![false, false, true, true, true, true, true, true, true, false]
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_on_mismatched_layout() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome = (1..=10).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=10).map(|n| -n as f32).collect();
+
+        SegmentCrossover::new(vec![3, 3]).crossover(&mut rng, &parent_a, &parent_b);
+    }
+}
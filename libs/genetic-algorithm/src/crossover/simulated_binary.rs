@@ -0,0 +1,74 @@
+use crate::*;
+
+/// SBX: simulates single-point crossover of binary strings on real genes;
+/// the larger `eta` (distribution index), the closer children stay to their
+/// parents.
+#[derive(Clone, Debug)]
+pub struct SimulatedBinaryCrossover {
+    eta: f32,
+}
+
+impl SimulatedBinaryCrossover {
+    pub fn new(eta: f32) -> Self {
+        Self::try_new(eta).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(eta: f32) -> Result<Self, GaError> {
+        GaError::check_non_negative("eta", eta)?;
+
+        Ok(Self { eta })
+    }
+}
+
+impl CrossoverMethod for SimulatedBinaryCrossover {
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome,
+        parent_b: &Chromosome,
+    ) -> Chromosome {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        let exponent = 1.0 / (self.eta + 1.0);
+
+        parent_a
+            .iter()
+            .zip(parent_b.iter())
+            .map(|(a, b)| {
+                let u = rng.gen::<f32>();
+
+                let beta = if u <= 0.5 {
+                    (2.0 * u).powf(exponent)
+                } else {
+                    (1.0 / (2.0 * (1.0 - u))).powf(exponent)
+                };
+
+                // SBX yields two siblings - pick one of them at random
+                if rng.gen_bool(0.5) {
+                    0.5 * ((1.0 + beta) * a + (1.0 - beta) * b)
+                } else {
+                    0.5 * ((1.0 - beta) * a + (1.0 + beta) * b)
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome = vec![1.0, 2.0, 3.0, 4.0].into_iter().collect();
+        let parent_b: Chromosome = vec![-1.0, 0.0, 5.0, 4.0].into_iter().collect();
+
+        let child = SimulatedBinaryCrossover::new(2.0).crossover(&mut rng, &parent_a, &parent_b);
+        let expected: Chromosome = vec![-0.72079206, 2.1070006, 2.9757462, 4.0].into_iter().collect();
+
+        assert_eq!(child, expected);
+    }
+}
@@ -0,0 +1,58 @@
+use crate::*;
+
+#[derive(Clone, Debug, Default)]
+pub struct SinglePointCrossover;
+
+impl<G> CrossoverMethod<G> for SinglePointCrossover
+    where
+        G: Copy,
+{
+    fn crossover(
+        &self,
+        rng: &mut dyn RngCore,
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
+        assert_eq!(parent_a.len(), parent_b.len());
+
+        if parent_a.len() < 2 {
+            return parent_a.clone();
+        }
+
+        let point = rng.gen_range(1..parent_a.len());
+
+        parent_a
+            .iter()
+            .take(point)
+            .chain(parent_b.iter().skip(point))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    #[test]
+    fn test() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let parent_a: Chromosome = (1..=100).map(|n| n as f32).collect();
+        let parent_b: Chromosome = (1..=100).map(|n| -n as f32).collect();
+
+        let child = SinglePointCrossover.crossover(&mut rng, &parent_a, &parent_b);
+
+        // Number of leading genes taken from `parent_a`
+        let point = child.iter().take_while(|&gene| gene > 0.0).count();
+
+        assert_eq!(point, 63);
+
+        // ... and all of the remaining genes come from `parent_b`
+        assert!(child
+            .iter()
+            .zip(parent_b.iter())
+            .skip(point)
+            .all(|(c, p)| c == p));
+    }
+}
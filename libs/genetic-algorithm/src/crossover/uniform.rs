@@ -3,13 +3,16 @@ use crate::*;
 #[derive(Clone, Debug, Default)]
 pub struct UniformCrossover;
 
-impl CrossoverMethod for UniformCrossover {
+impl<G> CrossoverMethod<G> for UniformCrossover
+    where
+        G: Copy,
+{
     fn crossover(
         &self,
         rng: &mut dyn RngCore,
-        parent_a: &Chromosome,
-        parent_b: &Chromosome,
-    ) -> Chromosome {
+        parent_a: &Chromosome<G>,
+        parent_b: &Chromosome<G>,
+    ) -> Chromosome<G> {
         assert_eq!(parent_a.len(), parent_b.len());
 
         let parent_a = parent_a.iter();
@@ -0,0 +1,286 @@
+use crate::*;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DifferentialVariant {
+    /// DE/rand/1/bin - the donor is built around a random individual.
+    #[default]
+    Rand1Bin,
+
+    /// DE/best/1/bin - the donor is built around the fittest individual,
+    /// which converges faster, but is more prone to getting stuck.
+    Best1Bin,
+}
+
+/// Differential evolution, which creates each trial chromosome by adding a
+/// scaled difference of two random chromosomes to a third one and mixing the
+/// result with the chromosome it competes against (its target).
+///
+/// Since a trial replaces its target only if it turns out to be at least as
+/// fit, the targets are kept between generations: each call to `evolve()`
+/// expects the population returned by the previous call, evaluated and in the
+/// same order.
+pub struct DifferentialEvolution {
+    variant: DifferentialVariant,
+    f: f32,
+    cr: f32,
+    targets: Vec<(Chromosome, f32)>,
+    constraint: Option<Box<dyn Constraint>>,
+    percentiles: Vec<f32>,
+}
+
+impl DifferentialEvolution {
+    /// - `f` scales the difference vector (usually within `0.4..=1.0`),
+    /// - `cr` is the probability of taking each gene from the donor.
+    pub fn new(variant: DifferentialVariant, f: f32, cr: f32) -> Self {
+        Self::try_new(variant, f, cr).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(variant: DifferentialVariant, f: f32, cr: f32) -> Result<Self, GaError> {
+        GaError::check_positive("f", f)?;
+        GaError::check_probability("cr", cr)?;
+
+        Ok(Self {
+            variant,
+            f,
+            cr,
+            targets: Vec::new(),
+            constraint: None,
+            percentiles: Vec::new(),
+        })
+    }
+
+    /// Repairs each trial (e.g. keeps its genes within `Bounds`).
+    pub fn with_constraint(mut self, constraint: impl Constraint + 'static) -> Self {
+        self.constraint = Some(Box::new(constraint));
+        self
+    }
+
+    /// Makes `Statistics` report fitness at given percentiles (each in
+    /// `0.0..=100.0`).
+    pub fn with_percentiles(self, percentiles: Vec<f32>) -> Self {
+        self.try_with_percentiles(percentiles)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_with_percentiles(mut self, percentiles: Vec<f32>) -> Result<Self, GaError> {
+        GaError::check_percentiles(&percentiles)?;
+
+        self.percentiles = percentiles;
+        Ok(self)
+    }
+
+    /// Returns the current targets together with their fitness.
+    pub fn targets(&self) -> &[(Chromosome, f32)] {
+        &self.targets
+    }
+
+    /// Lets given trials compete against their targets and creates the next
+    /// trials; the first call takes given population as the targets.
+    pub fn evolve<I>(&mut self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
+        where
+            I: Individual,
+    {
+        self.try_evolve(rng, population)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Like `evolve()`, but returns an error instead of panicking on invalid
+    /// input, e.g. too few individuals or empty chromosomes.
+    pub fn try_evolve<I>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> Result<(Vec<I>, Statistics), GaError>
+        where
+            I: Individual,
+    {
+        GaError::check(
+            population.len() >= 4,
+            "population size",
+            population.len() as f32,
+            "at least 4 for differential evolution",
+        )?;
+
+        let genes = match self.targets.first() {
+            Some((target, _)) => target.len(),
+            None => population[0].chromosome().len(),
+        };
+
+        GaError::check_positive("chromosome length", genes as f32)?;
+
+        for individual in population {
+            GaError::check_length(genes, individual.chromosome().len())?;
+        }
+
+        if self.targets.is_empty() {
+            self.targets = population
+                .iter()
+                .map(|i| (i.chromosome().clone(), i.fitness()))
+                .collect();
+        } else {
+            GaError::check(
+                population.len() == self.targets.len(),
+                "population size",
+                population.len() as f32,
+                "the same as in the previous generation",
+            )?;
+
+            for (target, trial) in self.targets.iter_mut().zip(population) {
+                if trial.fitness() >= target.1 {
+                    *target = (trial.chromosome().clone(), trial.fitness());
+                }
+            }
+        }
+
+        let trials = (0..self.targets.len())
+            .map(|idx| I::create(self.trial(rng, idx)))
+            .collect();
+
+        Ok((trials, Statistics::new(population, &self.percentiles)))
+    }
+
+    fn trial(&self, rng: &mut dyn RngCore, target: usize) -> Chromosome {
+        let (base, r1, r2) = match self.variant {
+            DifferentialVariant::Rand1Bin => {
+                let [base, r1, r2] = self.pick(rng, &[target]);
+
+                (base, r1, r2)
+            }
+
+            DifferentialVariant::Best1Bin => {
+                let best = self.best();
+                let [r1, r2] = self.pick(rng, &[target, best]);
+
+                (best, r1, r2)
+            }
+        };
+
+        let target = &self.targets[target].0;
+        let base = &self.targets[base].0;
+        let r1 = &self.targets[r1].0;
+        let r2 = &self.targets[r2].0;
+
+        // At least one gene is taken from the donor
+        let forced = rng.gen_range(0..target.len());
+
+        let mut trial = (0..target.len())
+            .map(|gene| {
+                if gene == forced || rng.gen_bool(self.cr as _) {
+                    base[gene] + self.f * (r1[gene] - r2[gene])
+                } else {
+                    target[gene]
+                }
+            })
+            .collect();
+
+        if let Some(constraint) = &self.constraint {
+            constraint.repair(&mut trial);
+        }
+
+        trial
+    }
+
+    /// Picks `N` distinct random targets, skipping the `excluded` ones.
+    fn pick<const N: usize>(&self, rng: &mut dyn RngCore, excluded: &[usize]) -> [usize; N] {
+        let mut picked = [0; N];
+
+        for idx in 0..N {
+            picked[idx] = loop {
+                let candidate = rng.gen_range(0..self.targets.len());
+
+                if !excluded.contains(&candidate) && !picked[..idx].contains(&candidate) {
+                    break candidate;
+                }
+            };
+        }
+
+        picked
+    }
+
+    fn best(&self) -> usize {
+        self.targets
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))
+            .map(|(idx, _)| idx)
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn population() -> Vec<TestIndividual> {
+        vec![
+            vec![0.0, 0.0, 0.0],
+            vec![1.0, 2.0, 1.0],
+            vec![1.0, 2.0, 4.0],
+            vec![2.0, 1.0, 0.0],
+            vec![3.0, 0.0, 1.0],
+        ]
+        .into_iter()
+        .map(|genes| TestIndividual::create(genes.into_iter().collect()))
+        .collect()
+    }
+
+    fn evolve(variant: DifferentialVariant) -> f32 {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut de = DifferentialEvolution::new(variant, 0.5, 0.9);
+        let mut population = population();
+
+        for _ in 0..10 {
+            population = de.evolve(&mut rng, &population).0;
+        }
+
+        de.targets().iter().map(|(_, fitness)| fitness).sum::<f32>() / 5.0
+    }
+
+    #[test]
+    fn rand_1_bin() {
+        // Started with an average fitness of 3.6
+        approx::assert_relative_eq!(evolve(DifferentialVariant::Rand1Bin), 10.79336);
+    }
+
+    #[test]
+    fn best_1_bin() {
+        approx::assert_relative_eq!(evolve(DifferentialVariant::Best1Bin), 10.984375);
+    }
+
+    #[test]
+    fn trials_never_replace_fitter_targets() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut de = DifferentialEvolution::new(DifferentialVariant::Rand1Bin, 0.5, 0.9);
+
+        de.evolve(&mut rng, &population());
+
+        let trials: Vec<_> = (0..5)
+            .map(|_| TestIndividual::create(vec![-1.0, 0.0, 0.0].into_iter().collect()))
+            .collect();
+        let (_, statistics) = de.evolve(&mut rng, &trials);
+
+        let fitnesses: Vec<_> = de.targets().iter().map(|(_, fitness)| *fitness).collect();
+
+        assert_eq!(fitnesses, vec![0.0, 4.0, 7.0, 3.0, 4.0]);
+        approx::assert_relative_eq!(statistics.max_fitness(), -1.0);
+    }
+
+    #[test]
+    fn try_evolve() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut de = DifferentialEvolution::new(DifferentialVariant::Rand1Bin, 0.5, 0.9);
+
+        let empty: Vec<_> = (0..5)
+            .map(|_| TestIndividual::create(Vec::new().into_iter().collect()))
+            .collect();
+
+        assert!(de.try_evolve(&mut rng, &empty).is_err());
+        assert!(de.try_evolve(&mut rng, &population()[..3]).is_err());
+
+        de.evolve(&mut rng, &population());
+
+        assert!(de.try_evolve(&mut rng, &population()[..4]).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use std::{error, fmt};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum GaError {
+    /// Got a population without any individuals.
+    EmptyPopulation,
+
+    /// Got a parameter outside of its domain, e.g. a negative probability.
+    InvalidParameter {
+        name: &'static str,
+        value: f32,
+        expected: &'static str,
+    },
+
+    /// Got chromosomes that should have had the same number of genes, but
+    /// don't.
+    LengthMismatch { expected: usize, actual: usize },
+}
+
+impl GaError {
+    /// Returns `InvalidParameter` unless `valid`.
+    pub fn check(
+        valid: bool,
+        name: &'static str,
+        value: f32,
+        expected: &'static str,
+    ) -> Result<(), Self> {
+        if valid {
+            Ok(())
+        } else {
+            Err(Self::InvalidParameter {
+                name,
+                value,
+                expected,
+            })
+        }
+    }
+
+    pub fn check_probability(name: &'static str, value: f32) -> Result<(), Self> {
+        Self::check(
+            (0.0..=1.0).contains(&value),
+            name,
+            value,
+            "a probability within 0.0..=1.0",
+        )
+    }
+
+    pub fn check_positive(name: &'static str, value: f32) -> Result<(), Self> {
+        Self::check(value > 0.0, name, value, "a positive number")
+    }
+
+    pub fn check_non_negative(name: &'static str, value: f32) -> Result<(), Self> {
+        Self::check(value >= 0.0, name, value, "a non-negative number")
+    }
+
+    pub fn check_percentiles(percentiles: &[f32]) -> Result<(), Self> {
+        for &percentile in percentiles {
+            Self::check(
+                (0.0..=100.0).contains(&percentile),
+                "percentile",
+                percentile,
+                "a number within 0.0..=100.0",
+            )?;
+        }
+
+        Ok(())
+    }
+
+    pub fn check_length(expected: usize, actual: usize) -> Result<(), Self> {
+        if expected == actual {
+            Ok(())
+        } else {
+            Err(Self::LengthMismatch { expected, actual })
+        }
+    }
+}
+
+impl fmt::Display for GaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::EmptyPopulation => write!(f, "got an empty population"),
+
+            Self::InvalidParameter {
+                name,
+                value,
+                expected,
+            } => write!(f, "invalid {}: got {}, expected {}", name, value, expected),
+
+            Self::LengthMismatch { expected, actual } => write!(
+                f,
+                "chromosome length mismatch: got {} genes, expected {}",
+                actual, expected
+            ),
+        }
+    }
+}
+
+impl error::Error for GaError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display() {
+        let err = GaError::check_probability("chance", 1.5).unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "invalid chance: got 1.5, expected a probability within 0.0..=1.0"
+        );
+    }
+}
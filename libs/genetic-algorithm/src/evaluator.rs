@@ -0,0 +1,143 @@
+/// Computes fitness of freshly created individuals, e.g. by simulating each
+/// of them on its own; see `GeneticAlgorithm::evolve_and_evaluate()`.
+pub trait Evaluator<I> {
+    fn evaluate(&self, population: &mut [I]);
+}
+
+/// Scores individuals one by one, with given function.
+#[derive(Clone, Debug)]
+pub struct SequentialEvaluator<F> {
+    score: F,
+}
+
+impl<F> SequentialEvaluator<F> {
+    pub fn new(score: F) -> Self {
+        Self { score }
+    }
+}
+
+impl<I, F> Evaluator<I> for SequentialEvaluator<F>
+    where
+        F: Fn(&mut I),
+{
+    fn evaluate(&self, population: &mut [I]) {
+        population.iter_mut().for_each(&self.score);
+    }
+}
+
+/// Scores individuals in parallel, across rayon's thread pool.
+#[cfg(feature = "parallel")]
+#[derive(Clone, Debug)]
+pub struct ParallelEvaluator<F> {
+    score: F,
+}
+
+#[cfg(feature = "parallel")]
+impl<F> ParallelEvaluator<F> {
+    pub fn new(score: F) -> Self {
+        Self { score }
+    }
+}
+
+#[cfg(feature = "parallel")]
+impl<I, F> Evaluator<I> for ParallelEvaluator<F>
+    where
+        I: Send,
+        F: Fn(&mut I) + Sync,
+{
+    fn evaluate(&self, population: &mut [I]) {
+        use rayon::prelude::*;
+
+        population.par_iter_mut().for_each(&self.score);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    struct EvaluatedIndividual {
+        chromosome: Chromosome,
+        fitness: f32,
+    }
+
+    impl Individual for EvaluatedIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self {
+                chromosome,
+                fitness: 0.0,
+            }
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.chromosome
+        }
+
+        fn fitness(&self) -> f32 {
+            self.fitness
+        }
+    }
+
+    fn population() -> Vec<EvaluatedIndividual> {
+        (1..=4)
+            .map(|n| EvaluatedIndividual {
+                chromosome: vec![n as f32; 3].into_iter().collect(),
+                fitness: 0.0,
+            })
+            .collect()
+    }
+
+    fn score(individual: &mut EvaluatedIndividual) {
+        individual.fitness = individual.chromosome.iter().product();
+    }
+
+    fn fitnesses(population: &[EvaluatedIndividual]) -> Vec<f32> {
+        population.iter().map(|i| i.fitness).collect()
+    }
+
+    #[test]
+    fn sequential() {
+        let mut population = population();
+
+        SequentialEvaluator::new(score).evaluate(&mut population);
+
+        assert_eq!(fitnesses(&population), vec![1.0, 8.0, 27.0, 64.0]);
+    }
+
+    #[test]
+    fn evolve_and_evaluate() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut population = population();
+
+        SequentialEvaluator::new(score).evaluate(&mut population);
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            UniformMutation::new(0.5, 0.5),
+        );
+
+        let evaluator = SequentialEvaluator::new(score);
+        let (population, _) = ga.evolve_and_evaluate(&mut rng, &population, &evaluator);
+
+        // Children have been scored right away
+        assert!(population.iter().all(|i| {
+            let expected: f32 = i.chromosome.iter().product();
+
+            i.fitness == expected
+        }));
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn parallel() {
+        let mut population = population();
+
+        ParallelEvaluator::new(score).evaluate(&mut population);
+
+        assert_eq!(fitnesses(&population), vec![1.0, 8.0, 27.0, 64.0]);
+    }
+}
@@ -0,0 +1,171 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+/// Remembers up to `capacity` best chromosomes ever seen, together with the
+/// generation they've been seen in, so that they don't get lost when the
+/// population moves on.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HallOfFame<G = f32> {
+    capacity: usize,
+    champions: Vec<Champion<G>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Champion<G = f32> {
+    pub generation: usize,
+    pub fitness: f32,
+    pub chromosome: Chromosome<G>,
+}
+
+impl<G> HallOfFame<G> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            champions: Vec::new(),
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the champions, best first.
+    pub fn champions(&self) -> &[Champion<G>] {
+        &self.champions
+    }
+
+    pub fn best(&self) -> Option<&Champion<G>> {
+        self.champions.first()
+    }
+
+    /// Records (evaluated) individuals of given generation that are fitter
+    /// than the worst champion; chromosomes that are already in the hall of
+    /// fame (e.g. elites) keep their original generation.
+    pub fn record<I>(&mut self, generation: usize, population: &[I])
+        where
+            I: Individual<G>,
+            G: Clone + GeneDistance,
+    {
+        for individual in population {
+            let fitness = individual.fitness();
+            let chromosome = individual.chromosome();
+
+            if self.champions.len() >= self.capacity
+                && self
+                    .champions
+                    .last()
+                    .is_none_or(|champion| champion.fitness >= fitness)
+            {
+                continue;
+            }
+
+            if self.champions.iter().any(|champion| {
+                champion.chromosome.len() == chromosome.len()
+                    && champion.chromosome.distance(chromosome) == 0.0
+            }) {
+                continue;
+            }
+
+            let idx = self
+                .champions
+                .iter()
+                .position(|champion| champion.fitness < fitness)
+                .unwrap_or(self.champions.len());
+
+            self.champions.insert(
+                idx,
+                Champion {
+                    generation,
+                    fitness,
+                    chromosome: chromosome.clone(),
+                },
+            );
+
+            self.champions.truncate(self.capacity);
+        }
+    }
+
+    /// Replaces the last `count` individuals of given population with the
+    /// `count` best champions; in a population returned by
+    /// `GeneticAlgorithm::evolve()` these are offspring, not survivors.
+    pub fn reinsert<I>(&self, population: &mut [I], count: usize)
+        where
+            I: Individual<G>,
+            G: Clone,
+    {
+        let count = count.min(self.champions.len()).min(population.len());
+        let offset = population.len() - count;
+
+        for (individual, champion) in population[offset..].iter_mut().zip(&self.champions) {
+            *individual = I::create(champion.chromosome.clone());
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String>
+        where
+            G: Serialize,
+    {
+        serde_json::to_string(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn population(fitnesses: &[f32]) -> Vec<TestIndividual> {
+        fitnesses
+            .iter()
+            .map(|&fitness| TestIndividual::create(vec![fitness].into_iter().collect()))
+            .collect()
+    }
+
+    fn fitnesses(hall_of_fame: &HallOfFame) -> Vec<(usize, f32)> {
+        hall_of_fame
+            .champions()
+            .iter()
+            .map(|champion| (champion.generation, champion.fitness))
+            .collect()
+    }
+
+    #[test]
+    fn record() {
+        let mut hall_of_fame = HallOfFame::new(3);
+
+        hall_of_fame.record(0, &population(&[1.0, 4.0, 2.0, 3.0]));
+        assert_eq!(fitnesses(&hall_of_fame), vec![(0, 4.0), (0, 3.0), (0, 2.0)]);
+
+        // 4.0 is the same chromosome as before, so it's not recorded again
+        hall_of_fame.record(1, &population(&[4.0, 3.5, 0.5]));
+        assert_eq!(fitnesses(&hall_of_fame), vec![(0, 4.0), (1, 3.5), (0, 3.0)]);
+
+        approx::assert_relative_eq!(hall_of_fame.best().unwrap().fitness, 4.0);
+    }
+
+    #[test]
+    fn reinsert() {
+        let mut hall_of_fame = HallOfFame::new(2);
+
+        hall_of_fame.record(0, &population(&[5.0, 7.0]));
+
+        let mut population = population(&[1.0, 2.0, 3.0, 4.0]);
+
+        hall_of_fame.reinsert(&mut population, 3);
+
+        let fitnesses: Vec<_> = population.iter().map(|i| i.fitness()).collect();
+
+        assert_eq!(fitnesses, vec![1.0, 2.0, 7.0, 5.0]);
+    }
+
+    #[test]
+    fn to_json() {
+        let mut hall_of_fame = HallOfFame::new(2);
+
+        hall_of_fame.record(3, &population(&[5.0]));
+
+        let json = hall_of_fame.to_json().unwrap();
+        let hall_of_fame: HallOfFame = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(fitnesses(&hall_of_fame), vec![(3, 5.0)]);
+    }
+}
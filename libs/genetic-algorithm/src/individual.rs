@@ -1,9 +1,53 @@
 use crate::*;
 
-pub trait Individual {
-    fn create(chromosome: Chromosome) -> Self;
-    fn chromosome(&self) -> &Chromosome;
+pub trait Individual<G = f32> {
+    fn create(chromosome: Chromosome<G>) -> Self;
+    fn chromosome(&self) -> &Chromosome<G>;
     fn fitness(&self) -> f32;
+
+    /// Returns a vector describing how this individual behaved (e.g. where it
+    /// ended up); used by novelty search, see `NoveltySearch`.
+    fn behaviour(&self) -> Vec<f32> {
+        Vec::new()
+    }
+}
+
+/// Individual seen through a score other than its own fitness (e.g. shared
+/// or novelty-based); only ever handed to the selection method.
+pub(crate) struct ScoredIndividual<'a, I> {
+    pub(crate) individual: &'a I,
+    pub(crate) score: f32,
+}
+
+impl<I, G> Individual<G> for ScoredIndividual<'_, I>
+    where
+        I: Individual<G>,
+{
+    fn create(_: Chromosome<G>) -> Self {
+        unreachable!("scored individuals are only used for selection")
+    }
+
+    fn chromosome(&self) -> &Chromosome<G> {
+        self.individual.chromosome()
+    }
+
+    fn fitness(&self) -> f32 {
+        self.score
+    }
+
+    fn behaviour(&self) -> Vec<f32> {
+        self.individual.behaviour()
+    }
+}
+
+/// Individual judged by several, possibly competing objectives (e.g. eating
+/// more while spending less energy) instead of a single fitness; see `Nsga2`.
+pub trait MultiObjectiveIndividual<G = f32> {
+    fn create(chromosome: Chromosome<G>) -> Self;
+    fn chromosome(&self) -> &Chromosome<G>;
+
+    /// Returns the fitness vector; every objective is maximized.
+    fn objectives(&self) -> Vec<f32>;
 }
 
 #[cfg(test)]
@@ -0,0 +1,284 @@
+use crate::*;
+use std::cmp::Ordering;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MigrationTopology {
+    /// Island `i` sends its migrants to island `i + 1`, and the last island
+    /// sends them back to the first one.
+    #[default]
+    Ring,
+
+    /// Each island sends its migrants to another, randomly chosen island.
+    Random,
+}
+
+/// Evolves several sub-populations (islands) independently, occasionally
+/// letting the best individuals of each island migrate to another one, which
+/// keeps the islands diverse while still sharing good solutions.
+pub struct IslandModel<S, G = f32> {
+    ga: GeneticAlgorithm<S, G>,
+    migration_interval: usize,
+    migrants: usize,
+    topology: MigrationTopology,
+}
+
+impl<S, G> IslandModel<S, G>
+    where
+        S: SelectionMethod,
+        G: Clone + GeneDistance,
+{
+    pub fn new(ga: GeneticAlgorithm<S, G>) -> Self {
+        Self {
+            ga,
+            migration_interval: 0,
+            migrants: 0,
+            topology: MigrationTopology::Ring,
+        }
+    }
+
+    /// Every `interval` generations, sends copies of the `migrants` best
+    /// individuals of each island to its neighbour, where they replace the
+    /// worst ones.
+    pub fn with_migration(
+        self,
+        interval: usize,
+        migrants: usize,
+        topology: MigrationTopology,
+    ) -> Self {
+        self.try_with_migration(interval, migrants, topology)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_with_migration(
+        mut self,
+        interval: usize,
+        migrants: usize,
+        topology: MigrationTopology,
+    ) -> Result<Self, GaError> {
+        GaError::check_positive("migration interval", interval as f32)?;
+
+        self.migration_interval = interval;
+        self.migrants = migrants;
+        self.topology = topology;
+        Ok(self)
+    }
+
+    /// Evolves given islands; `generation` is the number of the generation
+    /// being created (starting from 1), which decides whether migration
+    /// happens first.
+    ///
+    /// Returned statistics cover all the islands together, with species
+    /// counted separately on each island.
+    pub fn evolve<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        generation: usize,
+        islands: &[Vec<I>],
+    ) -> (Vec<Vec<I>>, Statistics<G>)
+        where
+            I: Individual<G> + Clone,
+    {
+        self.evolve_with(rng, generation, islands, None)
+    }
+
+    /// Evolves given islands, like `evolve()`, but selects parents by a mix
+    /// of fitness and novelty; all the islands share the same archive.
+    pub fn evolve_with_novelty<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        generation: usize,
+        islands: &[Vec<I>],
+        novelty: &mut NoveltySearch,
+    ) -> (Vec<Vec<I>>, Statistics<G>)
+        where
+            I: Individual<G> + Clone,
+    {
+        self.evolve_with(rng, generation, islands, Some(novelty))
+    }
+
+    fn evolve_with<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        generation: usize,
+        islands: &[Vec<I>],
+        mut novelty: Option<&mut NoveltySearch>,
+    ) -> (Vec<Vec<I>>, Statistics<G>)
+        where
+            I: Individual<G> + Clone,
+    {
+        assert!(!islands.is_empty());
+
+        let statistics = Statistics::new(islands.iter().flatten(), &self.ga.percentiles)
+            .with_mutation_rates(self.ga.mutation_method.rates());
+
+        let migrates =
+            self.migration_interval > 0 && generation.is_multiple_of(self.migration_interval);
+
+        let migrated;
+
+        let islands = if migrates {
+            migrated = self.migrate(rng, islands);
+            &migrated
+        } else {
+            islands
+        };
+
+        let (islands, island_statistics): (Vec<_>, Vec<_>) = islands
+            .iter()
+            .map(|island| self.ga.evolve_with(rng, island, novelty.as_deref_mut()))
+            .unzip();
+
+        let species: Option<usize> = island_statistics
+            .iter()
+            .map(|statistics| statistics.species())
+            .sum();
+
+        let statistics = match species {
+            Some(species) => statistics.with_species(species),
+            None => statistics,
+        };
+
+        (islands, statistics)
+    }
+
+    fn migrate<I>(&self, rng: &mut dyn RngCore, islands: &[Vec<I>]) -> Vec<Vec<I>>
+        where
+            I: Individual<G> + Clone,
+    {
+        let mut islands = islands.to_vec();
+        let len = islands.len();
+
+        if len < 2 || self.migrants == 0 {
+            return islands;
+        }
+
+        let mut immigrants: Vec<Vec<I>> = vec![Vec::new(); len];
+
+        for (from, island) in islands.iter().enumerate() {
+            let to = match self.topology {
+                MigrationTopology::Ring => (from + 1) % len,
+
+                MigrationTopology::Random => {
+                    let to = rng.gen_range(0..len - 1);
+
+                    if to >= from {
+                        to + 1
+                    } else {
+                        to
+                    }
+                }
+            };
+
+            immigrants[to].extend(
+                GeneticAlgorithm::<S, G>::best(island, self.migrants)
+                    .into_iter()
+                    .cloned(),
+            );
+        }
+
+        for (island, mut immigrants) in islands.iter_mut().zip(immigrants) {
+            immigrants.truncate(island.len());
+
+            island.sort_by(|a, b| {
+                b.fitness()
+                    .partial_cmp(&a.fitness())
+                    .unwrap_or(Ordering::Equal)
+            });
+
+            island.truncate(island.len() - immigrants.len());
+            island.extend(immigrants);
+        }
+
+        islands
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn island_model(topology: MigrationTopology) -> IslandModel<RouletteWheelSelection> {
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            UniformMutation::new(0.5, 0.5),
+        );
+
+        IslandModel::new(ga).with_migration(5, 2, topology)
+    }
+
+    fn islands() -> Vec<Vec<TestIndividual>> {
+        vec![
+            vec![1.0, 2.0, 3.0],
+            vec![10.0, 20.0, 30.0],
+            vec![100.0, 200.0, 300.0],
+        ]
+        .into_iter()
+        .map(|island| island.into_iter().map(TestIndividual::new).collect())
+        .collect()
+    }
+
+    fn fitnesses(islands: &[Vec<TestIndividual>]) -> Vec<Vec<f32>> {
+        islands
+            .iter()
+            .map(|island| island.iter().map(|i| i.fitness()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn ring_migration() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let islands = island_model(MigrationTopology::Ring).migrate(&mut rng, &islands());
+
+        let expected = vec![
+            vec![3.0, 300.0, 200.0],
+            vec![30.0, 3.0, 2.0],
+            vec![300.0, 30.0, 20.0],
+        ];
+
+        assert_eq!(fitnesses(&islands), expected);
+    }
+
+    #[test]
+    fn random_migration() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let islands = island_model(MigrationTopology::Random).migrate(&mut rng, &islands());
+
+        // Islands #1 and #2 have both picked island #0, whose population got
+        // entirely replaced by immigrants
+        let expected = vec![
+            vec![30.0, 20.0, 300.0],
+            vec![30.0, 20.0, 10.0],
+            vec![300.0, 3.0, 2.0],
+        ];
+
+        assert_eq!(fitnesses(&islands), expected);
+    }
+
+    #[test]
+    fn evolves_islands_independently_between_migrations() {
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            UniformMutation::new(0.5, 0.5),
+        );
+
+        let island = vec![
+            TestIndividual::create(vec![0.0, 0.0, 0.0].into_iter().collect()),
+            TestIndividual::create(vec![1.0, 2.0, 4.0].into_iter().collect()),
+        ];
+
+        let (expected, _) = ga.evolve(&mut ChaCha8Rng::from_seed(Default::default()), &island);
+
+        let (actual, statistics) = island_model(MigrationTopology::Ring).evolve(
+            &mut ChaCha8Rng::from_seed(Default::default()),
+            1,
+            &[island],
+        );
+
+        assert_eq!(actual, vec![expected]);
+        approx::assert_relative_eq!(statistics.max_fitness(), 7.0);
+    }
+}
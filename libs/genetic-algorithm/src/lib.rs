@@ -1,61 +1,346 @@
+mod bounds;
 mod chromosome;
+mod cma_es;
 mod crossover;
+mod differential_evolution;
+mod error;
+mod evaluator;
+mod hall_of_fame;
 mod individual;
+mod island_model;
+mod map_elites;
 mod mutation;
+mod novelty;
+mod nsga2;
+mod runner;
 mod selection;
+mod speciation;
 mod statistics;
+mod survivor_strategy;
 
+pub use self::bounds::*;
 pub use self::chromosome::*;
+pub use self::cma_es::*;
 pub use self::crossover::*;
+pub use self::differential_evolution::*;
+pub use self::error::*;
+pub use self::evaluator::*;
+pub use self::hall_of_fame::*;
 pub use self::individual::*;
+pub use self::island_model::*;
+pub use self::map_elites::*;
 pub use self::mutation::*;
+pub use self::novelty::*;
+pub use self::nsga2::*;
+pub use self::runner::*;
 pub use self::selection::*;
+use self::speciation::*;
 pub use self::statistics::*;
+pub use self::survivor_strategy::*;
 use rand::seq::SliceRandom;
 use rand::{Rng, RngCore};
+use std::cmp::Ordering;
 
-pub struct GeneticAlgorithm<S> {
+pub struct GeneticAlgorithm<S, G = f32> {
     selection_method: S,
-    crossover_method: Box<dyn CrossoverMethod>,
-    mutation_method: Box<dyn MutationMethod>,
+    crossover_method: Box<dyn CrossoverMethod<G>>,
+    mutation_method: Box<dyn MutationMethod<G>>,
+    elite_count: usize,
+    survivor_strategy: SurvivorStrategy,
+    speciation: Option<Speciation>,
+    constraint: Option<Box<dyn Constraint<G>>>,
+    percentiles: Vec<f32>,
 }
 
-impl<S> GeneticAlgorithm<S>
+impl<S, G> GeneticAlgorithm<S, G>
     where
         S: SelectionMethod,
+        G: Clone + GeneDistance,
 {
     pub fn new(
         selection_method: S,
-        crossover_method: impl CrossoverMethod + 'static,
-        mutation_method: impl MutationMethod + 'static,
+        crossover_method: impl CrossoverMethod<G> + 'static,
+        mutation_method: impl MutationMethod<G> + 'static,
     ) -> Self {
         Self {
             selection_method,
             crossover_method: Box::new(crossover_method),
             mutation_method: Box::new(mutation_method),
+            elite_count: 0,
+            survivor_strategy: SurvivorStrategy::Generational,
+            speciation: None,
+            constraint: None,
+            percentiles: Vec::new(),
         }
     }
 
-    pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics)
+    /// Copies `elite_count` best chromosomes into the next generation
+    /// unchanged.
+    pub fn with_elitism(mut self, elite_count: usize) -> Self {
+        self.elite_count = elite_count;
+        self
+    }
+
+    pub fn with_survivor_strategy(mut self, survivor_strategy: SurvivorStrategy) -> Self {
+        self.survivor_strategy = survivor_strategy;
+        self
+    }
+
+    /// Clusters the population into species of individuals that lie closer
+    /// than `threshold` to each other (see `Chromosome::distance()`) and
+    /// divides each individual's fitness by the size of its species before
+    /// selection.
+    ///
+    /// Survivors (elites and the `mu` best individuals) are still picked by
+    /// their raw fitness.
+    pub fn with_speciation(self, threshold: f32) -> Self {
+        self.try_with_speciation(threshold)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_with_speciation(mut self, threshold: f32) -> Result<Self, GaError> {
+        self.speciation = Some(Speciation::try_new(threshold)?);
+        Ok(self)
+    }
+
+    /// Repairs each offspring (e.g. keeps its genes within `Bounds`) after
+    /// crossover and again after mutation.
+    pub fn with_constraint(mut self, constraint: impl Constraint<G> + 'static) -> Self {
+        self.constraint = Some(Box::new(constraint));
+        self
+    }
+
+    /// Makes `Statistics` report fitness at given percentiles (each in
+    /// `0.0..=100.0`).
+    pub fn with_percentiles(self, percentiles: Vec<f32>) -> Self {
+        self.try_with_percentiles(percentiles)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_with_percentiles(mut self, percentiles: Vec<f32>) -> Result<Self, GaError> {
+        GaError::check_percentiles(&percentiles)?;
+
+        self.percentiles = percentiles;
+        Ok(self)
+    }
+
+    pub fn evolve<I>(&self, rng: &mut dyn RngCore, population: &[I]) -> (Vec<I>, Statistics<G>)
         where
-            I: Individual,
+            I: Individual<G>,
     {
-        assert!(!population.is_empty());
+        self.evolve_with(rng, population, None)
+    }
+
+    /// Like `evolve()`, but returns an error instead of panicking on invalid
+    /// input, e.g. an empty population or chromosomes of different lengths.
+    pub fn try_evolve<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> Result<(Vec<I>, Statistics<G>), GaError>
+        where
+            I: Individual<G>,
+    {
+        self.try_evolve_with(rng, population, None)
+    }
 
-        let new_population = (0..population.len())
+    /// Evolves given population, like `evolve()`, but selects parents by a mix
+    /// of fitness and novelty (see `NoveltySearch`).
+    pub fn evolve_with_novelty<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+        novelty: &mut NoveltySearch,
+    ) -> (Vec<I>, Statistics<G>)
+        where
+            I: Individual<G>,
+    {
+        self.evolve_with(rng, population, Some(novelty))
+    }
+
+    /// Evolves given population, like `evolve()`, and then lets `evaluator`
+    /// compute fitness of the new generation.
+    pub fn evolve_and_evaluate<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+        evaluator: &dyn Evaluator<I>,
+    ) -> (Vec<I>, Statistics<G>)
+        where
+            I: Individual<G>,
+    {
+        let (mut population, statistics) = self.evolve(rng, population);
+
+        evaluator.evaluate(&mut population);
+
+        (population, statistics)
+    }
+
+    pub(crate) fn evolve_with<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+        novelty: Option<&mut NoveltySearch>,
+    ) -> (Vec<I>, Statistics<G>)
+        where
+            I: Individual<G>,
+    {
+        self.try_evolve_with(rng, population, novelty)
+            .unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub(crate) fn try_evolve_with<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+        novelty: Option<&mut NoveltySearch>,
+    ) -> Result<(Vec<I>, Statistics<G>), GaError>
+        where
+            I: Individual<G>,
+    {
+        if population.is_empty() {
+            return Err(GaError::EmptyPopulation);
+        }
+
+        let survivor_count = match self.survivor_strategy {
+            SurvivorStrategy::Generational => self.elite_count,
+            SurvivorStrategy::MuPlusLambda { mu } => {
+                GaError::check_positive("mu", mu as f32)?;
+                mu.max(self.elite_count)
+            }
+        };
+
+        let survivors = Self::best(population, survivor_count);
+
+        // Scores that selection should use instead of the raw fitness
+        let mut scores = novelty.map(|novelty| novelty.scores(population));
+
+        let species = self.speciation.as_ref().map(|speciation| {
+            let scores = scores
+                .get_or_insert_with(|| population.iter().map(|i| i.fitness()).collect());
+
+            speciation.share(population, scores)
+        });
+
+        let scored: Option<Vec<_>> = scores.map(|scores| {
+            population
+                .iter()
+                .zip(scores)
+                .map(|(individual, score)| ScoredIndividual { individual, score })
+                .collect()
+        });
+
+        // Methods whose picks depend on each other (e.g.
+        // `StochasticUniversalSampling`) get the whole mating pool selected at
+        // once; others select the parents of each child separately
+        let mut mating_pool = match self.survivor_strategy {
+            SurvivorStrategy::Generational if self.selection_method.selects_in_batches() => {
+                let count = 2 * (population.len() - survivors.len());
+
+                let mating_pool: Vec<_> = match &scored {
+                    Some(scored) => self
+                        .selection_method
+                        .try_select_many(rng, scored, count)?
+                        .into_iter()
+                        .map(|parent| parent.individual)
+                        .collect(),
+
+                    None => self.selection_method.try_select_many(rng, population, count)?,
+                };
+
+                Some(mating_pool.into_iter())
+            }
+
+            _ => None,
+        };
+
+        let children = (survivors.len()..population.len())
             .map(|_| {
-                let parent_a = self.selection_method.select(rng, population).chromosome();
-                let parent_b = self.selection_method.select(rng, population).chromosome();
+                let (parent_a, parent_b) = match self.survivor_strategy {
+                    SurvivorStrategy::Generational => match &mut mating_pool {
+                        Some(mating_pool) => {
+                            (mating_pool.next().unwrap(), mating_pool.next().unwrap())
+                        }
+
+                        None => {
+                            let parents = match &scored {
+                                Some(scored) => self
+                                    .selection_method
+                                    .try_select_many(rng, scored, 2)?
+                                    .into_iter()
+                                    .map(|parent| parent.individual)
+                                    .collect(),
+
+                                None => self.selection_method.try_select_many(rng, population, 2)?,
+                            };
 
-                let mut child = self.crossover_method.crossover(rng, parent_a, parent_b);
+                            (parents[0], parents[1])
+                        }
+                    },
+
+                    SurvivorStrategy::MuPlusLambda { .. } => (
+                        *survivors.choose(rng).unwrap(),
+                        *survivors.choose(rng).unwrap(),
+                    ),
+                };
+
+                let mut child = self.crossover_method.try_crossover(
+                    rng,
+                    parent_a.chromosome(),
+                    parent_b.chromosome(),
+                )?;
+
+                self.repair(&mut child);
+                child.inherit_step_sizes(parent_a.chromosome(), parent_b.chromosome());
 
                 self.mutation_method.mutate(rng, &mut child);
+                self.repair(&mut child);
 
-                I::create(child)
+                Ok(I::create(child))
             })
+            .collect::<Result<Vec<_>, GaError>>()?;
+
+        let new_population = survivors
+            .iter()
+            .map(|survivor| I::create(survivor.chromosome().clone()))
+            .chain(children)
             .collect();
 
-        (new_population, Statistics::new(population))
+        let mut statistics = Statistics::new(population, &self.percentiles)
+            .with_mutation_rates(self.mutation_method.rates());
+
+        if let Some(species) = species {
+            statistics = statistics.with_species(species);
+        }
+
+        Ok((new_population, statistics))
+    }
+
+    fn repair(&self, chromosome: &mut Chromosome<G>) {
+        if let Some(constraint) = &self.constraint {
+            constraint.repair(chromosome);
+        }
+    }
+
+    /// Returns up to `count` fittest individuals, best first.
+    pub(crate) fn best<I>(population: &[I], count: usize) -> Vec<&I>
+        where
+            I: Individual<G>,
+    {
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let mut ranked: Vec<_> = population.iter().collect();
+
+        ranked.sort_by(|a, b| {
+            b.fitness()
+                .partial_cmp(&a.fitness())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        ranked.truncate(count);
+        ranked
     }
 }
 
@@ -77,7 +362,7 @@ mod tests {
         let ga = GeneticAlgorithm::new(
             RouletteWheelSelection,
             UniformCrossover,
-            GaussianMutation::new(0.5, 0.5),
+            UniformMutation::new(0.5, 0.5),
         );
 
         let mut population = vec![
@@ -100,4 +385,239 @@ mod tests {
 
         assert_eq!(population, expected_population);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn elitism() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            UniformMutation::new(1.0, 0.5),
+        )
+        .with_elitism(2);
+
+        let population = vec![
+            individual(&[0.0, 0.0, 0.0]),
+            individual(&[1.0, 2.0, 4.0]),
+            individual(&[1.0, 1.0, 1.0]),
+            individual(&[1.0, 2.0, 1.0]),
+        ];
+
+        let (population, _) = ga.evolve(&mut rng, &population);
+
+        assert_eq!(population.len(), 4);
+        assert_eq!(population[0], individual(&[1.0, 2.0, 4.0]));
+        assert_eq!(population[1], individual(&[1.0, 2.0, 1.0]));
+        assert_ne!(population[2], individual(&[1.0, 2.0, 4.0]));
+        assert_ne!(population[3], individual(&[1.0, 2.0, 4.0]));
+    }
+
+    #[test]
+    fn mu_plus_lambda() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            UniformMutation::new(0.5, 0.5),
+        )
+        .with_survivor_strategy(SurvivorStrategy::MuPlusLambda { mu: 2 });
+
+        let mut population = vec![
+            individual(&[0.0, 0.0, 0.0]),
+            individual(&[1.0, 1.0, 1.0]),
+            individual(&[1.0, 2.0, 1.0]),
+            individual(&[1.0, 2.0, 4.0]),
+        ];
+
+        let mut best_fitness = 7.0;
+
+        for _ in 0..10 {
+            population = ga.evolve(&mut rng, &population).0;
+
+            // Survivors are never worse than the best individuals of the
+            // previous generation
+            assert!(population[0].fitness() >= best_fitness);
+            best_fitness = population[0].fitness();
+        }
+
+        assert_eq!(population.len(), 4);
+    }
+
+    #[test]
+    fn speciation() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            UniformMutation::new(0.5, 0.5),
+        )
+        .with_speciation(0.5);
+
+        let population = vec![
+            individual(&[0.0, 0.0, 0.0]),
+            individual(&[1.0, 1.0, 1.0]),
+            individual(&[1.0, 1.0, 1.5]),
+            individual(&[1.0, 2.0, 4.0]),
+        ];
+
+        let (population, statistics) = ga.evolve(&mut rng, &population);
+
+        assert_eq!(population.len(), 4);
+        assert_eq!(statistics.species(), Some(3));
+        approx::assert_relative_eq!(statistics.max_fitness(), 7.0);
+    }
+
+    #[test]
+    fn discrete_genes() {
+        struct OneMax(Chromosome<bool>);
+
+        impl Individual<bool> for OneMax {
+            fn create(chromosome: Chromosome<bool>) -> Self {
+                Self(chromosome)
+            }
+
+            fn chromosome(&self) -> &Chromosome<bool> {
+                &self.0
+            }
+
+            fn fitness(&self) -> f32 {
+                self.0.iter().filter(|&gene| gene).count() as f32
+            }
+        }
+
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            TournamentSelection::new(2, 1.0),
+            UniformCrossover,
+            BitFlipMutation::new(0.05),
+        )
+        .with_elitism(1);
+
+        let mut population: Vec<_> = (0..10)
+            .map(|_| OneMax::create((0..8).map(|_| rng.gen_bool(0.5)).collect()))
+            .collect();
+
+        for _ in 0..20 {
+            population = ga.evolve(&mut rng, &population).0;
+        }
+
+        assert_eq!(population[0].fitness(), 8.0);
+    }
+
+    #[test]
+    fn constraint() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            BlendCrossover::new(0.5),
+            GaussianMutation::new(1.0, 5.0),
+        )
+        .with_constraint(Bounds::uniform(0.0..=1.0, Repair::Reflect));
+
+        let mut population: Vec<_> = (0..10)
+            .map(|_| TestIndividual::create(vec![0.5, 0.5, 0.5].into_iter().collect()))
+            .collect();
+
+        for _ in 0..10 {
+            population = ga.evolve(&mut rng, &population).0;
+        }
+
+        assert!(population
+            .iter()
+            .flat_map(|individual| individual.chromosome().iter())
+            .all(|gene| (0.0..=1.0).contains(&gene)));
+    }
+
+    #[test]
+    fn try_evolve() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            UniformMutation::new(0.5, 0.5),
+        );
+
+        let population: Vec<TestIndividual> = Vec::new();
+
+        assert_eq!(
+            ga.try_evolve(&mut rng, &population).unwrap_err(),
+            GaError::EmptyPopulation
+        );
+
+        assert_eq!(
+            RouletteWheelSelection
+                .try_select(&mut rng, &population)
+                .unwrap_err(),
+            GaError::EmptyPopulation
+        );
+
+        let population = vec![
+            TestIndividual::create(vec![1.0, 2.0].into_iter().collect()),
+            TestIndividual::create(vec![1.0, 2.0, 3.0].into_iter().collect()),
+        ];
+
+        // Whether the mismatch gets noticed depends on which parents get
+        // picked, so let's give it a few tries
+        let err = (0..10)
+            .find_map(|_| ga.try_evolve(&mut rng, &population).err())
+            .unwrap();
+
+        assert!(matches!(err, GaError::LengthMismatch { .. }));
+    }
+
+    #[test]
+    fn try_new() {
+        assert!(UniformMutation::try_new(0.5, 0.5).is_ok());
+
+        assert_eq!(
+            GaussianMutation::try_new(1.5, 0.5).unwrap_err(),
+            GaError::InvalidParameter {
+                name: "chance",
+                value: 1.5,
+                expected: "a probability within 0.0..=1.0",
+            }
+        );
+
+        assert!(TournamentSelection::try_new(0, 0.5).is_err());
+        assert!(KPointCrossover::try_new(0).is_err());
+        assert!(NoveltySearch::try_new(0, 0.5).is_err());
+        assert!(NoveltySearch::try_new(5, 1.5).is_err());
+        assert!(CmaEs::try_new(0.0).is_err());
+        assert!(DifferentialEvolution::try_new(DifferentialVariant::Rand1Bin, 0.5, 1.5).is_err());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        assert_eq!(
+            ga.try_with_percentiles(vec![50.0, 101.0]).err().map(|err| err.to_string()),
+            Some("invalid percentile: got 101, expected a number within 0.0..=100.0".into())
+        );
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        assert!(IslandModel::new(ga)
+            .try_with_migration(0, 2, MigrationTopology::Ring)
+            .is_err());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            GaussianMutation::new(0.5, 0.5),
+        );
+
+        assert!(ga.try_with_speciation(0.0).is_err());
+    }
+}
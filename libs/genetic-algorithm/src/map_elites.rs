@@ -0,0 +1,300 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+
+/// One axis of the behaviour space: values of given behaviour component
+/// between `min` and `max` get split into `bins` equal cells (values outside
+/// fall into the outermost ones).
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct BehaviourDimension {
+    pub min: f32,
+    pub max: f32,
+    pub bins: usize,
+}
+
+impl BehaviourDimension {
+    pub fn new(min: f32, max: f32, bins: usize) -> Self {
+        assert!(min < max);
+        assert!(bins > 0);
+
+        Self { min, max, bins }
+    }
+
+    fn bin(&self, value: f32) -> usize {
+        let bin = (value - self.min) / (self.max - self.min) * (self.bins as f32);
+
+        (bin.max(0.0) as usize).min(self.bins - 1)
+    }
+}
+
+/// MAP-Elites: instead of a single best solution, keeps the best individual
+/// (the elite) found for each cell of a grid spanned over the behaviour space
+/// (see `Individual::behaviour()`), and breeds offspring from random elites.
+pub struct MapElites<G = f32> {
+    crossover_method: Box<dyn CrossoverMethod<G>>,
+    mutation_method: Box<dyn MutationMethod<G>>,
+    archive: MapElitesArchive<G>,
+}
+
+impl<G> MapElites<G>
+    where
+        G: Clone,
+{
+    pub fn new(
+        dimensions: Vec<BehaviourDimension>,
+        crossover_method: impl CrossoverMethod<G> + 'static,
+        mutation_method: impl MutationMethod<G> + 'static,
+    ) -> Self {
+        Self {
+            crossover_method: Box::new(crossover_method),
+            mutation_method: Box::new(mutation_method),
+            archive: MapElitesArchive::new(dimensions),
+        }
+    }
+
+    pub fn archive(&self) -> &MapElitesArchive<G> {
+        &self.archive
+    }
+
+    /// Puts given (evaluated) individuals into the archive, each replacing
+    /// the elite of its cell if it's fitter.
+    pub fn insert<I>(&mut self, population: &[I])
+        where
+            I: Individual<G>,
+    {
+        for individual in population {
+            self.archive.insert(
+                individual.behaviour(),
+                individual.fitness(),
+                individual.chromosome(),
+            );
+        }
+    }
+
+    /// Breeds `count` children, each from two elites picked at random.
+    pub fn offspring<I>(&self, rng: &mut dyn RngCore, count: usize) -> Vec<I>
+        where
+            I: Individual<G>,
+    {
+        let elites: Vec<_> = self.archive.elites().collect();
+
+        assert!(!elites.is_empty(), "got an empty archive");
+
+        (0..count)
+            .map(|_| {
+                let parent_a = &elites.choose(rng).unwrap().chromosome;
+                let parent_b = &elites.choose(rng).unwrap().chromosome;
+
+                let mut child = self.crossover_method.crossover(rng, parent_a, parent_b);
+
+                child.inherit_step_sizes(parent_a, parent_b);
+
+                self.mutation_method.mutate(rng, &mut child);
+
+                I::create(child)
+            })
+            .collect()
+    }
+
+    /// Archives given population and replaces it with offspring of the
+    /// elites.
+    pub fn evolve<I>(&mut self, rng: &mut dyn RngCore, population: &[I]) -> Vec<I>
+        where
+            I: Individual<G>,
+    {
+        self.insert(population);
+        self.offspring(rng, population.len())
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct MapElitesArchive<G = f32> {
+    dimensions: Vec<BehaviourDimension>,
+    cells: Vec<Option<Elite<G>>>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Elite<G = f32> {
+    /// Bin along each of the dimensions.
+    pub cell: Vec<usize>,
+    pub behaviour: Vec<f32>,
+    pub fitness: f32,
+    pub chromosome: Chromosome<G>,
+}
+
+impl<G> MapElitesArchive<G> {
+    fn new(dimensions: Vec<BehaviourDimension>) -> Self {
+        assert!(!dimensions.is_empty());
+
+        let len = dimensions.iter().map(|dimension| dimension.bins).product();
+
+        Self {
+            dimensions,
+            cells: (0..len).map(|_| None).collect(),
+        }
+    }
+
+    pub fn dimensions(&self) -> &[BehaviourDimension] {
+        &self.dimensions
+    }
+
+    pub fn elites(&self) -> impl Iterator<Item = &Elite<G>> + '_ {
+        self.cells.iter().flatten()
+    }
+
+    /// Returns the elite of given cell, if that cell has been reached yet.
+    pub fn get(&self, cell: &[usize]) -> Option<&Elite<G>> {
+        self.cells[self.index(cell)].as_ref()
+    }
+
+    /// Returns the fraction of cells that have an elite.
+    pub fn coverage(&self) -> f32 {
+        (self.elites().count() as f32) / (self.cells.len() as f32)
+    }
+
+    pub fn to_json(&self) -> serde_json::Result<String>
+        where
+            G: Serialize,
+    {
+        serde_json::to_string(self)
+    }
+
+    fn insert(&mut self, behaviour: Vec<f32>, fitness: f32, chromosome: &Chromosome<G>)
+        where
+            G: Clone,
+    {
+        assert_eq!(behaviour.len(), self.dimensions.len());
+
+        let cell: Vec<_> = self
+            .dimensions
+            .iter()
+            .zip(&behaviour)
+            .map(|(dimension, &value)| dimension.bin(value))
+            .collect();
+
+        let idx = self.index(&cell);
+
+        if self.cells[idx]
+            .as_ref()
+            .is_some_and(|elite| elite.fitness >= fitness)
+        {
+            return;
+        }
+
+        self.cells[idx] = Some(Elite {
+            cell,
+            behaviour,
+            fitness,
+            chromosome: chromosome.clone(),
+        });
+    }
+
+    fn index(&self, cell: &[usize]) -> usize {
+        assert_eq!(cell.len(), self.dimensions.len());
+
+        cell.iter()
+            .zip(&self.dimensions)
+            .fold(0, |idx, (&bin, dimension)| {
+                assert!(bin < dimension.bins);
+
+                idx * dimension.bins + bin
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Behaves according to its first two genes.
+    struct TestMapElitesIndividual(Chromosome);
+
+    impl Individual for TestMapElitesIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self(chromosome)
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.0
+        }
+
+        fn fitness(&self) -> f32 {
+            self.0[2]
+        }
+
+        fn behaviour(&self) -> Vec<f32> {
+            vec![self.0[0], self.0[1]]
+        }
+    }
+
+    fn individual(genes: &[f32]) -> TestMapElitesIndividual {
+        TestMapElitesIndividual::create(genes.iter().cloned().collect())
+    }
+
+    fn map_elites() -> MapElites {
+        MapElites::new(
+            vec![
+                BehaviourDimension::new(0.0, 1.0, 2),
+                BehaviourDimension::new(0.0, 1.0, 4),
+            ],
+            UniformCrossover,
+            GaussianMutation::new(0.5, 0.1),
+        )
+    }
+
+    #[test]
+    fn insert() {
+        let mut map_elites = map_elites();
+
+        map_elites.insert(&[
+            individual(&[0.1, 0.1, 1.0]),
+            individual(&[0.2, 0.2, 3.0]),
+            individual(&[0.2, 0.1, 2.0]),
+            individual(&[0.9, 0.9, 1.0]),
+            individual(&[1.5, -1.0, 5.0]),
+        ]);
+
+        let archive = map_elites.archive();
+
+        assert_eq!(archive.elites().count(), 3);
+        approx::assert_relative_eq!(archive.coverage(), 3.0 / 8.0);
+        approx::assert_relative_eq!(archive.get(&[0, 0]).unwrap().fitness, 3.0);
+        approx::assert_relative_eq!(archive.get(&[1, 3]).unwrap().fitness, 1.0);
+        approx::assert_relative_eq!(archive.get(&[1, 0]).unwrap().fitness, 5.0);
+        assert!(archive.get(&[0, 1]).is_none());
+    }
+
+    #[test]
+    fn evolve() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut map_elites = map_elites();
+
+        let mut population: Vec<_> = (0..10)
+            .map(|_| individual(&[0.5, 0.5, 0.0]))
+            .collect();
+
+        for _ in 0..20 {
+            population = map_elites.evolve(&mut rng, &population);
+        }
+
+        assert_eq!(population.len(), 10);
+
+        // Mutations have spread elites over more cells than the one the
+        // population started in
+        assert!(map_elites.archive().elites().count() > 1);
+    }
+
+    #[test]
+    fn to_json() {
+        let mut map_elites = map_elites();
+
+        map_elites.insert(&[individual(&[0.1, 0.1, 1.0])]);
+
+        let json = map_elites.archive().to_json().unwrap();
+        let archive: MapElitesArchive = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(archive.elites().count(), 1);
+        assert_eq!(archive.get(&[0, 0]).unwrap().cell, vec![0, 0]);
+    }
+}
@@ -1,8 +1,37 @@
+mod adaptive;
+mod bit_flip;
 mod gaussian;
+mod integer;
+mod self_adaptive;
+mod uniform;
 
+pub use self::adaptive::*;
+pub use self::bit_flip::*;
 pub use self::gaussian::*;
+pub use self::integer::*;
+pub use self::self_adaptive::*;
+pub use self::uniform::*;
 use crate::*;
 
-pub trait MutationMethod {
-    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome);
-}
\ No newline at end of file
+pub trait MutationMethod<G = f32> {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>);
+
+    /// Returns the rates this mutation is currently performed with, if they
+    /// change over time (see `AdaptiveMutation`).
+    fn rates(&self) -> Option<MutationRates> {
+        None
+    }
+}
+
+impl<G, M> MutationMethod<G> for Box<M>
+    where
+        M: MutationMethod<G> + ?Sized,
+{
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        (**self).mutate(rng, child)
+    }
+
+    fn rates(&self) -> Option<MutationRates> {
+        (**self).rates()
+    }
+}
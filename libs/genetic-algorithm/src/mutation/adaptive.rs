@@ -0,0 +1,253 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::sync::Arc;
+
+/// How much the rates get scaled by after each generation; the classic value
+/// for the 1/5th success rule.
+const ADAPTATION_FACTOR: f32 = 1.22;
+
+/// Lower bound for both rates, so that mutation never stops entirely.
+const MIN_RATE: f32 = 1e-4;
+
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MutationRates {
+    pub chance: f32,
+    pub coeff: f32,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AdaptationRule {
+    /// Increases the rates when more than a fifth of the new generation beats
+    /// the best fifth of the previous one, and decreases them otherwise.
+    OneFifth,
+
+    /// Increases the rates when the max fitness hasn't improved for
+    /// `patience` generations, and relaxes them back towards the initial
+    /// ones once it does.
+    Stagnation { patience: usize },
+
+    /// Increases the rates when diversity (see `Statistics::diversity()`)
+    /// drops below `target`, and decreases them when it's above.
+    Diversity { target: f32 },
+}
+
+/// Wraps a mutation created by `build(chance, coeff)`, scaling both rates
+/// from the progress of the population reported through `adapt()`; the
+/// mutation gets rebuilt once per adaptation, not once per child.
+///
+/// Since the genetic algorithm doesn't keep any state between generations,
+/// this one is meant to be kept by the caller, adapted after each
+/// evaluation and cloned into the algorithm.
+#[derive(Clone)]
+pub struct AdaptiveMutation<G = f32> {
+    build: Arc<dyn Fn(f32, f32) -> Arc<dyn MutationMethod<G> + Send + Sync> + Send + Sync>,
+    mutation: Arc<dyn MutationMethod<G> + Send + Sync>,
+    rule: AdaptationRule,
+    initial: MutationRates,
+    rates: MutationRates,
+    threshold: Option<f32>,
+    best_fitness: f32,
+    stagnation: usize,
+}
+
+impl<G> AdaptiveMutation<G> {
+    pub fn new<F, M>(rule: AdaptationRule, chance: f32, coeff: f32, build: F) -> Self
+        where
+            F: Fn(f32, f32) -> M + Send + Sync + 'static,
+            M: MutationMethod<G> + Send + Sync + 'static,
+    {
+        Self::try_new(rule, chance, coeff, build).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new<F, M>(
+        rule: AdaptationRule,
+        chance: f32,
+        coeff: f32,
+        build: F,
+    ) -> Result<Self, GaError>
+        where
+            F: Fn(f32, f32) -> M + Send + Sync + 'static,
+            M: MutationMethod<G> + Send + Sync + 'static,
+    {
+        GaError::check_probability("chance", chance)?;
+        GaError::check_non_negative("coeff", coeff)?;
+
+        let build: Arc<dyn Fn(f32, f32) -> Arc<dyn MutationMethod<G> + Send + Sync> + Send + Sync> =
+            Arc::new(move |chance, coeff| Arc::new(build(chance, coeff)));
+
+        let rates = MutationRates { chance, coeff };
+
+        Ok(Self {
+            mutation: build(chance, coeff),
+            build,
+            rule,
+            initial: rates,
+            rates,
+            threshold: None,
+            best_fitness: f32::NEG_INFINITY,
+            stagnation: 0,
+        })
+    }
+
+    /// Returns the rates mutation is currently performed with.
+    pub fn rates(&self) -> MutationRates {
+        self.rates
+    }
+
+    /// Adjusts the rates given an evaluated generation.
+    pub fn adapt<'a, I>(&mut self, population: impl IntoIterator<Item = &'a I>)
+        where
+            I: Individual<G> + 'a,
+            G: GeneDistance,
+    {
+        let population: Vec<_> = population.into_iter().collect();
+
+        match self.rule {
+            AdaptationRule::OneFifth => {
+                let mut fitnesses: Vec<_> = population.iter().map(|i| i.fitness()).collect();
+
+                if let Some(threshold) = self.threshold {
+                    let successes = fitnesses
+                        .iter()
+                        .filter(|&&fitness| fitness > threshold)
+                        .count();
+
+                    self.scale((successes as f32) / (fitnesses.len() as f32) > 0.2);
+                }
+
+                fitnesses.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+                self.threshold = Some(Statistics::<G>::percentile_of(&fitnesses, 80.0));
+            }
+
+            AdaptationRule::Stagnation { patience } => {
+                let max_fitness = population
+                    .iter()
+                    .map(|i| i.fitness())
+                    .fold(f32::NEG_INFINITY, f32::max);
+
+                if max_fitness > self.best_fitness {
+                    self.best_fitness = max_fitness;
+                    self.stagnation = 0;
+
+                    self.scale(false);
+                    self.rates.chance = self.rates.chance.max(self.initial.chance);
+                    self.rates.coeff = self.rates.coeff.max(self.initial.coeff);
+                } else {
+                    self.stagnation += 1;
+
+                    if self.stagnation >= patience {
+                        self.stagnation = 0;
+                        self.scale(true);
+                    }
+                }
+            }
+
+            AdaptationRule::Diversity { target } => {
+                self.scale(Statistics::<G>::diversity_of(&population) < target);
+            }
+        }
+
+        self.mutation = (self.build)(self.rates.chance, self.rates.coeff);
+    }
+
+    fn scale(&mut self, up: bool) {
+        let factor = if up {
+            ADAPTATION_FACTOR
+        } else {
+            1.0 / ADAPTATION_FACTOR
+        };
+
+        self.rates.chance = (self.rates.chance * factor).clamp(MIN_RATE, 1.0);
+        self.rates.coeff = (self.rates.coeff * factor).max(MIN_RATE);
+    }
+}
+
+impl<G> MutationMethod<G> for AdaptiveMutation<G> {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<G>) {
+        self.mutation.mutate(rng, child)
+    }
+
+    fn rates(&self) -> Option<MutationRates> {
+        Some(self.rates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mutation(rule: AdaptationRule) -> AdaptiveMutation {
+        AdaptiveMutation::new(rule, 0.1, 0.5, UniformMutation::new)
+    }
+
+    fn population(fitnesses: &[f32]) -> Vec<TestIndividual> {
+        fitnesses
+            .iter()
+            .map(|&fitness| TestIndividual::create(vec![fitness].into_iter().collect()))
+            .collect()
+    }
+
+    #[test]
+    fn one_fifth() {
+        let mut mutation = mutation(AdaptationRule::OneFifth);
+
+        mutation.adapt(&population(&[1.0, 2.0, 3.0, 4.0, 5.0]));
+        assert_eq!(mutation.rates(), MutationRates { chance: 0.1, coeff: 0.5 });
+
+        // Two out of five beat the previous 80th percentile
+        mutation.adapt(&population(&[1.0, 2.0, 3.0, 5.0, 6.0]));
+        approx::assert_relative_eq!(mutation.rates().chance, 0.122);
+        approx::assert_relative_eq!(mutation.rates().coeff, 0.61);
+
+        // None does
+        mutation.adapt(&population(&[1.0, 2.0, 3.0, 4.0, 5.0]));
+        approx::assert_relative_eq!(mutation.rates().chance, 0.1);
+        approx::assert_relative_eq!(mutation.rates().coeff, 0.5);
+    }
+
+    #[test]
+    fn stagnation() {
+        let mut mutation = mutation(AdaptationRule::Stagnation { patience: 2 });
+        let population = population(&[1.0, 2.0]);
+
+        mutation.adapt(&population);
+        mutation.adapt(&population);
+        assert_eq!(mutation.rates(), MutationRates { chance: 0.1, coeff: 0.5 });
+
+        mutation.adapt(&population);
+        approx::assert_relative_eq!(mutation.rates().chance, 0.122);
+
+        mutation.adapt(&self::population(&[3.0]));
+        approx::assert_relative_eq!(mutation.rates().chance, 0.1);
+    }
+
+    #[test]
+    fn diversity() {
+        let mut mutation = mutation(AdaptationRule::Diversity { target: 0.5 });
+
+        mutation.adapt(&population(&[1.0, 1.0]));
+        approx::assert_relative_eq!(mutation.rates().chance, 0.122);
+
+        mutation.adapt(&population(&[1.0, 3.0]));
+        mutation.adapt(&population(&[1.0, 3.0]));
+        approx::assert_relative_eq!(mutation.rates().chance, 0.1 / 1.22);
+    }
+
+    #[test]
+    fn reports_rates() {
+        let mutation: Box<dyn MutationMethod> = Box::new(mutation(AdaptationRule::OneFifth));
+
+        assert_eq!(mutation.rates(), Some(MutationRates { chance: 0.1, coeff: 0.5 }));
+    }
+
+    #[test]
+    fn try_new() {
+        let build = |chance, coeff| UniformMutation::new(chance, coeff);
+
+        assert!(AdaptiveMutation::try_new(AdaptationRule::OneFifth, 0.1, 0.5, build).is_ok());
+        assert!(AdaptiveMutation::try_new(AdaptationRule::OneFifth, 1.5, 0.5, build).is_err());
+        assert!(AdaptiveMutation::try_new(AdaptationRule::OneFifth, 0.1, -0.5, build).is_err());
+    }
+}
@@ -0,0 +1,75 @@
+use crate::*;
+
+/// Negates each gene with probability `chance`.
+#[derive(Clone, Debug)]
+pub struct BitFlipMutation {
+    chance: f32,
+}
+
+impl BitFlipMutation {
+    pub fn new(chance: f32) -> Self {
+        Self::try_new(chance).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(chance: f32) -> Result<Self, GaError> {
+        GaError::check_probability("chance", chance)?;
+
+        Ok(Self { chance })
+    }
+}
+
+impl MutationMethod<bool> for BitFlipMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<bool>) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as _) {
+                *gene = !*gene;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32) -> Vec<bool> {
+        let mut child = vec![true, false, true, false, true].into_iter().collect();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        BitFlipMutation::new(chance).mutate(&mut rng, &mut child);
+
+        child.iter().collect()
+    }
+
+    mod given_zero_chance {
+        #[test]
+        fn does_not_change_the_original_chromosome() {
+            let actual = super::actual(0.0);
+            let expected = vec![true, false, true, false, true];
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    mod given_fifty_fifty_chance {
+        #[test]
+        fn slightly_changes_the_original_chromosome() {
+            let actual = super::actual(0.5);
+            let expected = vec![true, false, false, true, false];
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    mod given_max_chance {
+        #[test]
+        fn negates_the_original_chromosome() {
+            let actual = super::actual(1.0);
+            let expected = vec![false, true, false, true, false];
+
+            assert_eq!(actual, expected);
+        }
+    }
+}
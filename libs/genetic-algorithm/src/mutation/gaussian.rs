@@ -1,26 +1,32 @@
 use crate::*;
+use rand_distr::StandardNormal;
 
+/// Adds noise sampled from a normal distribution with standard deviation
+/// `sigma` to each gene with probability `chance`.
 #[derive(Clone, Debug)]
 pub struct GaussianMutation {
     chance: f32,
-    coeff: f32,
+    sigma: f32,
 }
 
 impl GaussianMutation {
-    pub fn new(chance: f32, coeff: f32) -> Self {
-        assert!((0.0..=1.0).contains(&chance));
+    pub fn new(chance: f32, sigma: f32) -> Self {
+        Self::try_new(chance, sigma).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(chance: f32, sigma: f32) -> Result<Self, GaError> {
+        GaError::check_probability("chance", chance)?;
+        GaError::check_non_negative("sigma", sigma)?;
 
-        Self { chance, coeff }
+        Ok(Self { chance, sigma })
     }
 }
 
 impl MutationMethod for GaussianMutation {
     fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
         for gene in child.iter_mut() {
-            let sign = if rng.gen_bool(0.5) { -1.0 } else { 1.0 };
-
             if rng.gen_bool(self.chance as _) {
-                *gene += sign * self.coeff * rng.gen::<f32>();
+                *gene += self.sigma * rng.sample::<f32, _>(StandardNormal);
             }
         }
     }
@@ -32,102 +38,61 @@ mod tests {
     use rand::SeedableRng;
     use rand_chacha::ChaCha8Rng;
 
-    fn actual(chance: f32, coeff: f32) -> Vec<f32> {
+    fn actual(chance: f32, sigma: f32) -> Vec<f32> {
         let mut child = vec![1.0, 2.0, 3.0, 4.0, 5.0].into_iter().collect();
         let mut rng = ChaCha8Rng::from_seed(Default::default());
 
-        GaussianMutation::new(chance, coeff).mutate(&mut rng, &mut child);
+        GaussianMutation::new(chance, sigma).mutate(&mut rng, &mut child);
 
         child.iter().collect()
     }
 
     mod given_zero_chance {
-        fn actual(coeff: f32) -> Vec<f32> {
-            super::actual(0.0, coeff)
-        }
+        #[test]
+        fn does_not_change_the_original_chromosome() {
+            let actual = super::actual(0.0, 0.5);
+            let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
 
-        mod and_zero_coefficient {
-            use super::*;
-
-            #[test]
-            fn does_not_change_the_original_chromosome() {
-                let actual = actual(0.0);
-                let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
-
-                approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
-            }
-        }
-
-        mod and_nonzero_coefficient {
-            use super::*;
-
-            #[test]
-            fn does_not_change_the_original_chromosome() {
-                let actual = actual(0.5);
-                let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
-
-                approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
-            }
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
         }
     }
 
-    mod given_fifty_fifty_chance {
-        fn actual(coeff: f32) -> Vec<f32> {
-            super::actual(0.5, coeff)
-        }
-
-        mod and_zero_coefficient {
-            use super::*;
-
-            #[test]
-            fn does_not_change_the_original_chromosome() {
-                let actual = actual(0.0);
-                let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+    mod given_zero_sigma {
+        #[test]
+        fn does_not_change_the_original_chromosome() {
+            let actual = super::actual(1.0, 0.0);
+            let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
 
-                approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
-            }
-        }
-
-        mod and_nonzero_coefficient {
-            use super::*;
-
-            #[test]
-            fn slightly_changes_the_original_chromosome() {
-                let actual = actual(0.5);
-                let expected = vec![1.0, 1.7756249, 3.0, 4.1596804, 5.0];
-
-                approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
-            }
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
         }
     }
 
     mod given_max_chance {
-        fn actual(coeff: f32) -> Vec<f32> {
-            super::actual(1.0, coeff)
-        }
+        #[test]
+        fn entirely_changes_the_original_chromosome() {
+            let actual = super::actual(1.0, 0.5);
+            let expected = vec![1.6888486, 2.2026734, 2.4018655, 3.0324764, 4.664113];
 
-        mod and_zero_coefficient {
-            use super::*;
+            approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        }
+    }
 
-            #[test]
-            fn does_not_change_the_original_chromosome() {
-                let actual = actual(0.0);
-                let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+    #[test]
+    fn samples_a_normal_distribution() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child: Chromosome = vec![0.0; 10_000].into_iter().collect();
 
-                approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
-            }
-        }
+        GaussianMutation::new(1.0, 2.0).mutate(&mut rng, &mut child);
 
-        mod and_nonzero_coefficient {
-            use super::*;
+        let len = child.len() as f32;
+        let mean = child.iter().sum::<f32>() / len;
+        let std_dev = (child.iter().map(|gene| (gene - mean).powi(2)).sum::<f32>() / len).sqrt();
 
-            #[test]
-            fn entirely_changes_the_original_chromosome() {
-                let actual = actual(0.5);
-                let expected = vec![1.4545316, 2.1162078, 2.7756248, 3.9505124, 4.638691];
+        // About 68% of samples lie within one standard deviation from the mean
+        let within_one_sigma = child.iter().filter(|gene| gene.abs() <= 2.0).count();
 
-                approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
-            }
-        }
+        approx::assert_abs_diff_eq!(mean, 0.0, epsilon = 0.05);
+        approx::assert_abs_diff_eq!(std_dev, 2.0, epsilon = 0.05);
+        approx::assert_abs_diff_eq!(within_one_sigma as f32 / len, 0.68, epsilon = 0.01);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,95 @@
+use crate::*;
+use std::ops::RangeInclusive;
+
+/// Moves each gene, with probability `chance`, up or down by at most `step`,
+/// keeping it within `bounds` - meant for discrete traits such as the number
+/// of neurons or eye cells.
+#[derive(Clone, Debug)]
+pub struct IntegerMutation {
+    chance: f32,
+    bounds: RangeInclusive<i32>,
+    step: i32,
+}
+
+impl IntegerMutation {
+    pub fn new(chance: f32, bounds: RangeInclusive<i32>, step: i32) -> Self {
+        Self::try_new(chance, bounds, step).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(chance: f32, bounds: RangeInclusive<i32>, step: i32) -> Result<Self, GaError> {
+        GaError::check_probability("chance", chance)?;
+
+        GaError::check(
+            !bounds.is_empty(),
+            "bounds",
+            *bounds.start() as f32,
+            "a start not greater than the end",
+        )?;
+
+        GaError::check_positive("step", step as f32)?;
+
+        Ok(Self {
+            chance,
+            bounds,
+            step,
+        })
+    }
+}
+
+impl MutationMethod<i32> for IntegerMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome<i32>) {
+        for gene in child.iter_mut() {
+            if rng.gen_bool(self.chance as _) {
+                let sign = if rng.gen_bool(0.5) { -1 } else { 1 };
+                let delta = sign * rng.gen_range(1..=self.step);
+
+                *gene = gene
+                    .saturating_add(delta)
+                    .clamp(*self.bounds.start(), *self.bounds.end());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn actual(chance: f32, bounds: RangeInclusive<i32>, step: i32) -> Vec<i32> {
+        let mut child = vec![1, 2, 3, 4, 5].into_iter().collect();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        IntegerMutation::new(chance, bounds, step).mutate(&mut rng, &mut child);
+
+        child.iter().collect()
+    }
+
+    mod given_zero_chance {
+        #[test]
+        fn does_not_change_the_original_chromosome() {
+            let actual = super::actual(0.0, 0..=10, 3);
+            let expected = vec![1, 2, 3, 4, 5];
+
+            assert_eq!(actual, expected);
+        }
+    }
+
+    mod given_max_chance {
+        #[test]
+        fn entirely_changes_the_original_chromosome() {
+            let actual = super::actual(1.0, 0..=10, 3);
+            let expected = vec![4, 3, 1, 3, 2];
+
+            assert_eq!(actual, expected);
+        }
+
+        #[test]
+        fn keeps_genes_within_bounds() {
+            let actual = super::actual(1.0, 2..=4, 10);
+
+            assert!(actual.iter().all(|gene| (2..=4).contains(gene)));
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use crate::*;
+use rand_distr::StandardNormal;
+
+/// Smallest step size a chromosome can adapt down to, so that mutation never
+/// freezes entirely.
+const MIN_STEP_SIZE: f32 = 1e-5;
+
+/// Gaussian mutation whose standard deviations (step sizes) are carried by
+/// each chromosome and evolve along with it, as in evolution strategies.
+///
+/// Before the genes get mutated, step sizes are perturbed log-normally:
+///
+/// - with a single step size: `sigma *= exp(tau0 * N(0, 1))`,
+/// - with a step size per gene: `sigma_i *= exp(tau' * N(0, 1) + tau * N_i(0, 1))`.
+///
+/// Chromosomes without step sizes (e.g. the initial population) start with
+/// `sigma`.
+#[derive(Clone, Debug)]
+pub struct SelfAdaptiveMutation {
+    chance: f32,
+    sigma: f32,
+    per_gene: bool,
+}
+
+impl SelfAdaptiveMutation {
+    /// Creates a mutation with one step size shared by all genes.
+    pub fn new(chance: f32, sigma: f32) -> Self {
+        Self::try_new(chance, sigma).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(chance: f32, sigma: f32) -> Result<Self, GaError> {
+        GaError::check_probability("chance", chance)?;
+        GaError::check_positive("sigma", sigma)?;
+
+        Ok(Self {
+            chance,
+            sigma,
+            per_gene: false,
+        })
+    }
+
+    /// Creates a mutation with a separate step size for each gene.
+    pub fn per_gene(chance: f32, sigma: f32) -> Self {
+        Self::try_per_gene(chance, sigma).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_per_gene(chance: f32, sigma: f32) -> Result<Self, GaError> {
+        Ok(Self {
+            per_gene: true,
+            ..Self::try_new(chance, sigma)?
+        })
+    }
+}
+
+impl MutationMethod for SelfAdaptiveMutation {
+    fn mutate(&self, rng: &mut dyn RngCore, child: &mut Chromosome) {
+        if child.is_empty() {
+            return;
+        }
+
+        let len = child.len();
+        let step_sizes_len = if self.per_gene { len } else { 1 };
+
+        if child.step_sizes().len() != step_sizes_len {
+            *child.step_sizes_mut() = vec![self.sigma; step_sizes_len];
+        }
+
+        let n = len as f32;
+
+        if self.per_gene {
+            let tau_global = 1.0 / (2.0 * n).sqrt();
+            let tau_local = 1.0 / (2.0 * n.sqrt()).sqrt();
+            let global = tau_global * rng.sample::<f32, _>(StandardNormal);
+
+            for step_size in child.step_sizes_mut() {
+                let local = tau_local * rng.sample::<f32, _>(StandardNormal);
+
+                *step_size = (*step_size * (global + local).exp()).max(MIN_STEP_SIZE);
+            }
+        } else {
+            let tau = 1.0 / n.sqrt();
+            let step_size = &mut child.step_sizes_mut()[0];
+
+            *step_size =
+                (*step_size * (tau * rng.sample::<f32, _>(StandardNormal)).exp()).max(MIN_STEP_SIZE);
+        }
+
+        let step_sizes = child.step_sizes().to_vec();
+
+        for (idx, gene) in child.iter_mut().enumerate() {
+            if rng.gen_bool(self.chance as _) {
+                let step_size = step_sizes[if self.per_gene { idx } else { 0 }];
+
+                *gene += step_size * rng.sample::<f32, _>(StandardNormal);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn chromosome() -> Chromosome {
+        vec![1.0, 2.0, 3.0, 4.0, 5.0].into_iter().collect()
+    }
+
+    #[test]
+    fn single_step_size() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child = chromosome();
+
+        SelfAdaptiveMutation::new(1.0, 0.5).mutate(&mut rng, &mut child);
+
+        let actual: Vec<_> = child.iter().collect();
+        let expected = vec![1.3752981, 0.8924115, 1.2083995, 3.3780253, 4.0143046];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        approx::assert_relative_eq!(child.step_sizes(), [0.9258692].as_slice());
+    }
+
+    #[test]
+    fn per_gene_step_sizes() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child = chromosome();
+
+        SelfAdaptiveMutation::per_gene(1.0, 0.5).mutate(&mut rng, &mut child);
+
+        let actual: Vec<_> = child.iter().collect();
+        let expected = vec![-1.0121167, 1.5598338, 2.8933418, 4.221422, 5.1705956];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+
+        approx::assert_relative_eq!(
+            child.step_sizes(),
+            [0.93631405, 0.43904135, 0.30958942, 0.5626243, 0.4672425].as_slice()
+        );
+    }
+
+    #[test]
+    fn step_sizes_are_inherited() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut child = chromosome().with_step_sizes(vec![1e-9]);
+
+        SelfAdaptiveMutation::new(1.0, 0.5).mutate(&mut rng, &mut child);
+
+        // The tiny step size carried by the chromosome has been used instead
+        // of the initial one (and clamped to the minimum)
+        let actual: Vec<_> = child.iter().collect();
+        let expected = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+
+        approx::assert_abs_diff_eq!(actual.as_slice(), expected.as_slice(), epsilon = 1e-4);
+        approx::assert_relative_eq!(child.step_sizes(), [MIN_STEP_SIZE].as_slice());
+    }
+}
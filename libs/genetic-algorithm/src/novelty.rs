@@ -0,0 +1,193 @@
+use crate::*;
+use std::cmp::Ordering;
+
+/// Novelty search: rewards individuals for behaving differently from the rest
+/// of the population and from the behaviours seen in past generations, which
+/// keeps evolution going when fitness is deceptive or flat.
+///
+/// Novelty of an individual is the mean distance between its behaviour (see
+/// `Individual::behaviour()`) and the `k` nearest behaviours among the other
+/// individuals and the archive; the most novel behaviour of each generation
+/// gets archived.
+#[derive(Clone, Debug)]
+pub struct NoveltySearch {
+    k: usize,
+    weight: f32,
+    archive: Vec<Vec<f32>>,
+}
+
+impl NoveltySearch {
+    /// Creates novelty search that selects individuals by a mix of novelty
+    /// and fitness: `weight` of 0.0 means fitness only, 1.0 - novelty only.
+    pub fn new(k: usize, weight: f32) -> Self {
+        Self::try_new(k, weight).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(k: usize, weight: f32) -> Result<Self, GaError> {
+        GaError::check_positive("k", k as f32)?;
+        GaError::check_probability("weight", weight)?;
+
+        Ok(Self {
+            k,
+            weight,
+            archive: Vec::new(),
+        })
+    }
+
+    pub fn archive(&self) -> &[Vec<f32>] {
+        &self.archive
+    }
+
+    /// Returns novelty of each of given behaviours.
+    pub fn novelties(&self, behaviours: &[Vec<f32>]) -> Vec<f32> {
+        behaviours
+            .iter()
+            .enumerate()
+            .map(|(idx, behaviour)| {
+                let mut distances: Vec<_> = behaviours
+                    .iter()
+                    .enumerate()
+                    .filter(|(other_idx, _)| *other_idx != idx)
+                    .map(|(_, other)| other)
+                    .chain(&self.archive)
+                    .map(|other| Self::distance(behaviour, other))
+                    .collect();
+
+                if distances.is_empty() {
+                    return 0.0;
+                }
+
+                distances.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+                distances.truncate(self.k);
+
+                distances.iter().sum::<f32>() / (distances.len() as f32)
+            })
+            .collect()
+    }
+
+    /// Returns selection scores mixing normalized fitness and novelty, and
+    /// archives the most novel behaviour.
+    pub(crate) fn scores<I, G>(&mut self, population: &[I]) -> Vec<f32>
+        where
+            I: Individual<G>,
+    {
+        let behaviours: Vec<_> = population.iter().map(|i| i.behaviour()).collect();
+        let novelties = Self::normalize(self.novelties(&behaviours));
+        let fitnesses = Self::normalize(population.iter().map(|i| i.fitness()).collect());
+
+        let most_novel = novelties
+            .iter()
+            .enumerate()
+            .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+            .map(|(idx, _)| idx);
+
+        if let Some(idx) = most_novel {
+            self.archive.push(behaviours[idx].clone());
+        }
+
+        fitnesses
+            .into_iter()
+            .zip(novelties)
+            .map(|(fitness, novelty)| (1.0 - self.weight) * fitness + self.weight * novelty)
+            .collect()
+    }
+
+    fn distance(a: &[f32], b: &[f32]) -> f32 {
+        assert_eq!(a.len(), b.len());
+
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| (a - b).powi(2))
+            .sum::<f32>()
+            .sqrt()
+    }
+
+    /// Rescales given values into `0.0..=1.0`.
+    fn normalize(values: Vec<f32>) -> Vec<f32> {
+        let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+        let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+
+        if max > min {
+            values.iter().map(|value| (value - min) / (max - min)).collect()
+        } else {
+            vec![0.0; values.len()]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct BehavingIndividual {
+        behaviour: Vec<f32>,
+        fitness: f32,
+    }
+
+    impl Individual for BehavingIndividual {
+        fn create(_: Chromosome) -> Self {
+            panic!("not supported for BehavingIndividual")
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            panic!("not supported for BehavingIndividual")
+        }
+
+        fn fitness(&self) -> f32 {
+            self.fitness
+        }
+
+        fn behaviour(&self) -> Vec<f32> {
+            self.behaviour.clone()
+        }
+    }
+
+    fn behaviours() -> Vec<Vec<f32>> {
+        vec![vec![0.0, 0.0], vec![0.0, 1.0], vec![0.0, 2.0], vec![3.0, 4.0]]
+    }
+
+    #[test]
+    fn novelties() {
+        let actual = NoveltySearch::new(2, 0.5).novelties(&behaviours());
+        let expected = vec![1.5, 1.0, 1.5, (13.0_f32.sqrt() + 18.0_f32.sqrt()) / 2.0];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn novelties_with_archive() {
+        let mut novelty = NoveltySearch::new(1, 0.5);
+
+        novelty.archive.push(vec![3.0, 4.5]);
+
+        let actual = novelty.novelties(&behaviours());
+        let expected = vec![1.0, 1.0, 1.0, 0.5];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn scores() {
+        let population: Vec<_> = behaviours()
+            .into_iter()
+            .zip([4.0, 3.0, 2.0, 0.0])
+            .map(|(behaviour, fitness)| BehavingIndividual { behaviour, fitness })
+            .collect();
+
+        let mut novelty = NoveltySearch::new(2, 0.5);
+        let scores = novelty.scores(&population);
+
+        // Normalized novelty of the first and the third individual
+        let novelty_0 = (1.5 - 1.0) / ((13.0_f32.sqrt() + 18.0_f32.sqrt()) / 2.0 - 1.0);
+
+        let expected = vec![
+            0.5 * 1.0 + 0.5 * novelty_0,
+            0.5 * 0.75 + 0.5 * 0.0,
+            0.5 * 0.5 + 0.5 * novelty_0,
+            0.5 * 0.0 + 0.5 * 1.0,
+        ];
+
+        approx::assert_relative_eq!(scores.as_slice(), expected.as_slice());
+        assert_eq!(novelty.archive(), &[vec![3.0, 4.0]]);
+    }
+}
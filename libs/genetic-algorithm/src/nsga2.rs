@@ -0,0 +1,332 @@
+use crate::*;
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+/// NSGA-II: multi-objective evolution driven by Pareto dominance.
+///
+/// The evaluated population is expected to contain both the survivors of the
+/// previous generation and their children, as in (mu + lambda); its better
+/// half (by non-domination rank, then by crowding distance) survives
+/// unchanged, and the rest is filled with children of survivors picked by
+/// binary crowded tournaments.
+pub struct Nsga2<G = f32> {
+    crossover_method: Box<dyn CrossoverMethod<G>>,
+    mutation_method: Box<dyn MutationMethod<G>>,
+}
+
+impl<G> Nsga2<G>
+    where
+        G: Clone,
+{
+    pub fn new(
+        crossover_method: impl CrossoverMethod<G> + 'static,
+        mutation_method: impl MutationMethod<G> + 'static,
+    ) -> Self {
+        Self {
+            crossover_method: Box::new(crossover_method),
+            mutation_method: Box::new(mutation_method),
+        }
+    }
+
+    pub fn evolve<I>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &[I],
+    ) -> (Vec<I>, ParetoStatistics<G>)
+        where
+            I: MultiObjectiveIndividual<G>,
+    {
+        assert!(!population.is_empty());
+
+        let objectives: Vec<_> = population.iter().map(|i| i.objectives()).collect();
+        let fronts = non_dominated_sort(&objectives);
+
+        let mut ranks = vec![0; population.len()];
+        let mut crowding = vec![0.0; population.len()];
+
+        for (rank, front) in fronts.iter().enumerate() {
+            for (&idx, distance) in front.iter().zip(crowding_distance(&objectives, front)) {
+                ranks[idx] = rank;
+                crowding[idx] = distance;
+            }
+        }
+
+        // Lower rank first, then the less crowded one
+        let crowded_cmp = |a: &usize, b: &usize| {
+            ranks[*a].cmp(&ranks[*b]).then_with(|| {
+                crowding[*b]
+                    .partial_cmp(&crowding[*a])
+                    .unwrap_or(Ordering::Equal)
+            })
+        };
+
+        let mut survivors: Vec<_> = (0..population.len()).collect();
+
+        survivors.sort_by(crowded_cmp);
+        survivors.truncate(population.len().div_ceil(2));
+
+        let tournament = |rng: &mut dyn RngCore| {
+            let a = *survivors.choose(rng).unwrap();
+            let b = *survivors.choose(rng).unwrap();
+
+            if crowded_cmp(&b, &a) == Ordering::Less {
+                &population[b]
+            } else {
+                &population[a]
+            }
+        };
+
+        let children: Vec<_> = (survivors.len()..population.len())
+            .map(|_| {
+                let parent_a = tournament(rng).chromosome();
+                let parent_b = tournament(rng).chromosome();
+
+                let mut child = self.crossover_method.crossover(rng, parent_a, parent_b);
+
+                child.inherit_step_sizes(parent_a, parent_b);
+
+                self.mutation_method.mutate(rng, &mut child);
+
+                I::create(child)
+            })
+            .collect();
+
+        let new_population = survivors
+            .iter()
+            .map(|&idx| I::create(population[idx].chromosome().clone()))
+            .chain(children)
+            .collect();
+
+        let statistics = ParetoStatistics {
+            fronts: fronts.len(),
+            pareto_front: fronts[0]
+                .iter()
+                .map(|&idx| ParetoSolution {
+                    objectives: objectives[idx].clone(),
+                    chromosome: population[idx].chromosome().clone(),
+                })
+                .collect(),
+        };
+
+        (new_population, statistics)
+    }
+}
+
+/// Returns whether `a` is at least as good as `b` in every objective, and
+/// strictly better in at least one.
+pub fn dominates(a: &[f32], b: &[f32]) -> bool {
+    assert_eq!(a.len(), b.len());
+
+    a.iter().zip(b).all(|(a, b)| a >= b) && a.iter().zip(b).any(|(a, b)| a > b)
+}
+
+/// Splits given fitness vectors into Pareto fronts, best first; each front
+/// lists indices into `objectives`.
+pub fn non_dominated_sort(objectives: &[Vec<f32>]) -> Vec<Vec<usize>> {
+    let len = objectives.len();
+
+    // For each solution: how many solutions dominate it, and which solutions
+    // it dominates
+    let mut domination_counts = vec![0; len];
+    let mut dominated: Vec<Vec<usize>> = vec![Vec::new(); len];
+
+    for a in 0..len {
+        for b in 0..len {
+            if dominates(&objectives[a], &objectives[b]) {
+                dominated[a].push(b);
+                domination_counts[b] += 1;
+            }
+        }
+    }
+
+    let mut fronts = Vec::new();
+    let mut front: Vec<_> = (0..len).filter(|&idx| domination_counts[idx] == 0).collect();
+
+    while !front.is_empty() {
+        let mut next = Vec::new();
+
+        for &a in &front {
+            for &b in &dominated[a] {
+                domination_counts[b] -= 1;
+
+                if domination_counts[b] == 0 {
+                    next.push(b);
+                }
+            }
+        }
+
+        next.sort_unstable();
+        fronts.push(front);
+        front = next;
+    }
+
+    fronts
+}
+
+/// Returns crowding distance of each solution of given front (in the same
+/// order), i.e. how far its neighbours lie; solutions at the boundaries of
+/// the front get infinity.
+pub fn crowding_distance(objectives: &[Vec<f32>], front: &[usize]) -> Vec<f32> {
+    let mut distances = vec![0.0; front.len()];
+
+    if front.is_empty() {
+        return distances;
+    }
+
+    // Values of each objective across the front
+    let columns = (0..objectives[front[0]].len()).map(|objective| {
+        front
+            .iter()
+            .map(|&idx| objectives[idx][objective])
+            .collect::<Vec<_>>()
+    });
+
+    for values in columns {
+        let mut order: Vec<_> = (0..front.len()).collect();
+
+        order.sort_by(|&a, &b| values[a].partial_cmp(&values[b]).unwrap_or(Ordering::Equal));
+
+        let (first, last) = (order[0], order[order.len() - 1]);
+        let range = values[last] - values[first];
+
+        distances[first] = f32::INFINITY;
+        distances[last] = f32::INFINITY;
+
+        if range <= 0.0 {
+            continue;
+        }
+
+        for window in order.windows(3) {
+            distances[window[1]] += (values[window[2]] - values[window[0]]) / range;
+        }
+    }
+
+    distances
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ParetoStatistics<G = f32> {
+    fronts: usize,
+    pareto_front: Vec<ParetoSolution<G>>,
+}
+
+impl<G> ParetoStatistics<G> {
+    /// Returns the number of fronts the population has been sorted into.
+    pub fn fronts(&self) -> usize {
+        self.fronts
+    }
+
+    /// Returns the non-dominated solutions of the evaluated population.
+    pub fn pareto_front(&self) -> &[ParetoSolution<G>] {
+        &self.pareto_front
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ParetoSolution<G = f32> {
+    pub objectives: Vec<f32>,
+    pub chromosome: Chromosome<G>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Maximizes both the sum and the negated spread of its genes.
+    #[derive(Debug)]
+    struct TestMultiObjectiveIndividual(Chromosome);
+
+    impl MultiObjectiveIndividual for TestMultiObjectiveIndividual {
+        fn create(chromosome: Chromosome) -> Self {
+            Self(chromosome)
+        }
+
+        fn chromosome(&self) -> &Chromosome {
+            &self.0
+        }
+
+        fn objectives(&self) -> Vec<f32> {
+            let max = self.0.iter().fold(f32::MIN, f32::max);
+            let min = self.0.iter().fold(f32::MAX, f32::min);
+
+            vec![self.0.iter().sum(), min - max]
+        }
+    }
+
+    fn objectives() -> Vec<Vec<f32>> {
+        vec![
+            vec![1.0, 5.0],
+            vec![2.0, 2.0],
+            vec![3.0, 3.0],
+            vec![5.0, 1.0],
+            vec![1.0, 1.0],
+            vec![2.0, 1.0],
+        ]
+    }
+
+    #[test]
+    fn dominance() {
+        assert!(dominates(&[2.0, 2.0], &[1.0, 2.0]));
+        assert!(!dominates(&[2.0, 2.0], &[2.0, 2.0]));
+        assert!(!dominates(&[3.0, 1.0], &[1.0, 3.0]));
+    }
+
+    #[test]
+    fn non_dominated_sorting() {
+        let fronts = non_dominated_sort(&objectives());
+
+        assert_eq!(fronts, vec![vec![0, 2, 3], vec![1], vec![5], vec![4]]);
+    }
+
+    #[test]
+    fn crowding() {
+        let distances = crowding_distance(&objectives(), &[0, 2, 3]);
+
+        assert_eq!(distances[0], f32::INFINITY);
+        approx::assert_relative_eq!(distances[1], (5.0 - 1.0) / 4.0 + (5.0 - 1.0) / 4.0);
+        assert_eq!(distances[2], f32::INFINITY);
+    }
+
+    #[test]
+    fn evolve() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let nsga2 = Nsga2::new(UniformCrossover, GaussianMutation::new(0.5, 0.5));
+
+        let mut population: Vec<_> = (0..20)
+            .map(|_| {
+                TestMultiObjectiveIndividual::create(
+                    (0..3).map(|_| rng.gen_range(-1.0..1.0)).collect(),
+                )
+            })
+            .collect();
+
+        let initial_front = nsga2.evolve(&mut rng, &population).1;
+        let mut statistics = initial_front.clone();
+
+        for _ in 0..20 {
+            (population, statistics) = nsga2.evolve(&mut rng, &population);
+        }
+
+        assert_eq!(population.len(), 20);
+
+        // The front has moved towards larger sums and smaller spreads
+        let best_sum = |statistics: &ParetoStatistics| {
+            statistics
+                .pareto_front()
+                .iter()
+                .map(|solution| solution.objectives[0])
+                .fold(f32::MIN, f32::max)
+        };
+
+        assert!(best_sum(&statistics) > best_sum(&initial_front));
+
+        // No solution of the front dominates another one
+        for a in statistics.pareto_front() {
+            for b in statistics.pareto_front() {
+                assert!(!dominates(&a.objectives, &b.objectives));
+            }
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use crate::*;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Termination {
+    /// Stops after given number of generations.
+    MaxGenerations(usize),
+
+    /// Stops once the max fitness reaches given value.
+    TargetFitness(f32),
+
+    /// Stops when the max fitness hasn't improved for given number of
+    /// generations.
+    Stagnation(usize),
+
+    /// Stops once given time has elapsed; checked only between generations,
+    /// so the last generation can overshoot it.
+    ///
+    /// Relies on `std::time::Instant`, which isn't available on
+    /// `wasm32-unknown-unknown`.
+    TimeLimit(Duration),
+}
+
+/// Summary of a finished `Runner::run()`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RunOutcome {
+    pub generations: usize,
+    pub best_fitness: f32,
+
+    /// The condition that has stopped the run.
+    pub termination: Termination,
+}
+
+type Observer<'a, G> = Box<dyn FnMut(usize, &Statistics<G>) + 'a>;
+
+/// Drives evolution generation by generation until one of the termination
+/// conditions is met, notifying observers after each generation.
+///
+/// What a generation is, is up to the caller - e.g. a call to
+/// `GeneticAlgorithm::evolve()` or to `Simulation::train()`.
+pub struct Runner<'a, G = f32> {
+    terminations: Vec<Termination>,
+    observers: Vec<Observer<'a, G>>,
+}
+
+impl<'a, G> Runner<'a, G> {
+    pub fn new() -> Self {
+        Self {
+            terminations: Vec::new(),
+            observers: Vec::new(),
+        }
+    }
+
+    /// Adds a termination condition; the run stops as soon as any of them is
+    /// met.
+    pub fn with_termination(mut self, termination: Termination) -> Self {
+        self.terminations.push(termination);
+        self
+    }
+
+    /// Adds a callback, invoked with the number of each generation (starting
+    /// from 0) and its statistics.
+    pub fn with_observer(mut self, observer: impl FnMut(usize, &Statistics<G>) + 'a) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
+    /// Calls `generation` until a termination condition is met.
+    pub fn run(&mut self, mut generation: impl FnMut() -> Statistics<G>) -> RunOutcome {
+        assert!(
+            !self.terminations.is_empty(),
+            "a run without any termination condition would never stop"
+        );
+
+        let started_at = self
+            .terminations
+            .iter()
+            .any(|termination| matches!(termination, Termination::TimeLimit(_)))
+            .then(Instant::now);
+
+        let mut best_fitness = f32::NEG_INFINITY;
+        let mut stagnation = 0;
+        let mut generations = 0;
+
+        loop {
+            let statistics = generation();
+
+            for observer in &mut self.observers {
+                observer(generations, &statistics);
+            }
+
+            generations += 1;
+
+            if statistics.max_fitness() > best_fitness {
+                best_fitness = statistics.max_fitness();
+                stagnation = 0;
+            } else {
+                stagnation += 1;
+            }
+
+            let termination = self.terminations.iter().find(|termination| match termination {
+                Termination::MaxGenerations(max) => generations >= *max,
+                Termination::TargetFitness(target) => best_fitness >= *target,
+                Termination::Stagnation(max) => stagnation >= *max,
+                Termination::TimeLimit(limit) => started_at.unwrap().elapsed() >= *limit,
+            });
+
+            if let Some(&termination) = termination {
+                return RunOutcome {
+                    generations,
+                    best_fitness,
+                    termination,
+                };
+            }
+        }
+    }
+}
+
+impl<G> Default for Runner<'_, G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Returns statistics of a single-individual population with given
+    /// fitness for each subsequent generation.
+    fn generations(fitnesses: Vec<f32>) -> impl FnMut() -> Statistics {
+        let mut fitnesses = fitnesses.into_iter();
+
+        move || {
+            let genes = vec![fitnesses.next().unwrap()].into_iter().collect();
+
+            Statistics::new(&[TestIndividual::create(genes)], &[])
+        }
+    }
+
+    #[test]
+    fn max_generations() {
+        let outcome = Runner::new()
+            .with_termination(Termination::MaxGenerations(3))
+            .run(generations(vec![1.0, 2.0, 3.0, 4.0]));
+
+        assert_eq!(
+            outcome,
+            RunOutcome {
+                generations: 3,
+                best_fitness: 3.0,
+                termination: Termination::MaxGenerations(3),
+            }
+        );
+    }
+
+    #[test]
+    fn target_fitness() {
+        let outcome = Runner::new()
+            .with_termination(Termination::MaxGenerations(10))
+            .with_termination(Termination::TargetFitness(2.5))
+            .run(generations(vec![1.0, 2.0, 3.0, 4.0]));
+
+        assert_eq!(outcome.generations, 3);
+        assert_eq!(outcome.termination, Termination::TargetFitness(2.5));
+    }
+
+    #[test]
+    fn stagnation() {
+        let outcome = Runner::new()
+            .with_termination(Termination::Stagnation(2))
+            .run(generations(vec![1.0, 3.0, 2.0, 3.0, 4.0]));
+
+        assert_eq!(outcome.generations, 4);
+        assert_eq!(outcome.best_fitness, 3.0);
+    }
+
+    #[test]
+    fn time_limit() {
+        let outcome = Runner::new()
+            .with_termination(Termination::TimeLimit(Duration::ZERO))
+            .run(generations(vec![1.0]));
+
+        assert_eq!(outcome.generations, 1);
+    }
+
+    #[test]
+    fn observers() {
+        let mut observed = Vec::new();
+
+        Runner::new()
+            .with_termination(Termination::MaxGenerations(2))
+            .with_observer(|generation, statistics| {
+                observed.push((generation, statistics.max_fitness()))
+            })
+            .run(generations(vec![5.0, 6.0]));
+
+        assert_eq!(observed, vec![(0, 5.0), (1, 6.0)]);
+    }
+
+    #[test]
+    fn drives_genetic_algorithm() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let ga = GeneticAlgorithm::new(
+            RouletteWheelSelection,
+            UniformCrossover,
+            UniformMutation::new(0.5, 0.5),
+        );
+
+        let mut population: Vec<_> = (0..10)
+            .map(|_| TestIndividual::create(vec![0.0, 0.0, 0.0].into_iter().collect()))
+            .collect();
+
+        let outcome = Runner::new()
+            .with_termination(Termination::MaxGenerations(100))
+            .with_termination(Termination::TargetFitness(3.0))
+            .run(|| {
+                let (new_population, statistics) = ga.evolve(&mut rng, &population);
+
+                population = new_population;
+                statistics
+            });
+
+        assert_eq!(outcome.termination, Termination::TargetFitness(3.0));
+        assert!(outcome.generations < 100);
+    }
+}
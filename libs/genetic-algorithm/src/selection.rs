@@ -1,10 +1,73 @@
+mod rank;
 mod roulette_wheel;
+mod stochastic_universal_sampling;
+mod tournament;
 
+pub use self::rank::*;
 pub use self::roulette_wheel::*;
+pub use self::stochastic_universal_sampling::*;
+pub use self::tournament::*;
 use crate::*;
 
 pub trait SelectionMethod {
-    fn select<'a, I>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+    fn select<'a, I, G>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
         where
-            I: Individual;
-}
\ No newline at end of file
+            I: Individual<G>;
+
+    /// Like `select()`, but returns an error instead of panicking on an empty
+    /// population.
+    fn try_select<'a, I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+    ) -> Result<&'a I, GaError>
+        where
+            I: Individual<G>,
+    {
+        if population.is_empty() {
+            return Err(GaError::EmptyPopulation);
+        }
+
+        Ok(self.select(rng, population))
+    }
+
+    /// Selects `count` individuals at once; methods that sample the whole
+    /// mating pool in one go (e.g. `StochasticUniversalSampling`) override
+    /// this, along with `selects_in_batches()`.
+    fn select_many<'a, I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Vec<&'a I>
+        where
+            I: Individual<G>,
+    {
+        (0..count).map(|_| self.select(rng, population)).collect()
+    }
+
+    /// Like `select_many()`, but returns an error instead of panicking on an
+    /// empty population.
+    fn try_select_many<'a, I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Result<Vec<&'a I>, GaError>
+        where
+            I: Individual<G>,
+    {
+        if population.is_empty() {
+            return Err(GaError::EmptyPopulation);
+        }
+
+        Ok(self.select_many(rng, population, count))
+    }
+
+    /// Whether `GeneticAlgorithm` should select its whole mating pool with a
+    /// single call to `select_many()`, rather than calling it for the parents
+    /// of each child.
+    fn selects_in_batches(&self) -> bool {
+        false
+    }
+}
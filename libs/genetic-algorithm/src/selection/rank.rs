@@ -0,0 +1,187 @@
+use crate::*;
+use rand::distributions::{Distribution, WeightedIndex};
+use std::cmp::Ordering;
+
+#[derive(Clone, Copy, Debug)]
+pub enum RankPressure {
+    /// Weights grow linearly with rank; the value is the expected number of
+    /// copies of the best individual and must be within `1.0..=2.0`.
+    Linear(f32),
+
+    /// Weights decay geometrically from the best individual downwards; the
+    /// value is the decay base and must be within `0.0..1.0`.
+    Exponential(f32),
+}
+
+#[derive(Clone, Debug)]
+pub struct RankSelection {
+    pressure: RankPressure,
+}
+
+impl RankSelection {
+    pub fn new(pressure: RankPressure) -> Self {
+        Self::try_new(pressure).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(pressure: RankPressure) -> Result<Self, GaError> {
+        match pressure {
+            RankPressure::Linear(pressure) => GaError::check(
+                (1.0..=2.0).contains(&pressure),
+                "rank pressure",
+                pressure,
+                "a number within 1.0..=2.0",
+            )?,
+
+            RankPressure::Exponential(base) => GaError::check(
+                base > 0.0 && base < 1.0,
+                "rank base",
+                base,
+                "a number within 0.0..1.0",
+            )?,
+        }
+
+        Ok(Self { pressure })
+    }
+
+    pub fn linear(pressure: f32) -> Self {
+        Self::new(RankPressure::Linear(pressure))
+    }
+
+    pub fn exponential(base: f32) -> Self {
+        Self::new(RankPressure::Exponential(base))
+    }
+
+    /// Returns the selection weight of an individual with given `rank`, where
+    /// rank 0 is the worst individual and `len - 1` is the best one.
+    fn weight(&self, rank: usize, len: usize) -> f32 {
+        match self.pressure {
+            RankPressure::Linear(pressure) => {
+                if len == 1 {
+                    1.0
+                } else {
+                    (2.0 - pressure) + 2.0 * (pressure - 1.0) * (rank as f32) / ((len - 1) as f32)
+                }
+            }
+
+            RankPressure::Exponential(base) => base.powi((len - 1 - rank) as i32),
+        }
+    }
+}
+
+impl SelectionMethod for RankSelection {
+    fn select<'a, I, G>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+        where
+            I: Individual<G>,
+    {
+        self.select_many(rng, population, 1).remove(0)
+    }
+
+    /// Ranks the population once and draws all `count` individuals from the
+    /// same ranking.
+    fn select_many<'a, I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Vec<&'a I>
+        where
+            I: Individual<G>,
+    {
+        let mut ranked: Vec<_> = population.iter().collect();
+
+        ranked.sort_by(|a, b| {
+            a.fitness()
+                .partial_cmp(&b.fitness())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let len = ranked.len();
+
+        let ranks = WeightedIndex::new((0..len).map(|rank| self.weight(rank, len).max(0.00001)))
+            .expect("got an empty population");
+
+        (0..count).map(|_| ranked[ranks.sample(rng)]).collect()
+    }
+
+    fn selects_in_batches(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::collections::BTreeMap;
+
+    fn population() -> Vec<TestIndividual> {
+        // The outlier doesn't dominate the selection - only its rank matters
+        vec![
+            TestIndividual::new(2.0),
+            TestIndividual::new(1.0),
+            TestIndividual::new(400.0),
+            TestIndividual::new(3.0),
+        ]
+    }
+
+    fn histogram<'a>(
+        selected: impl IntoIterator<Item = &'a TestIndividual>,
+    ) -> BTreeMap<i32, usize> {
+        selected
+            .into_iter()
+            .fold(BTreeMap::default(), |mut histogram, individual| {
+                *histogram.entry(individual.fitness() as i32).or_default() += 1;
+                histogram
+            })
+    }
+
+    fn select(selection: RankSelection) -> BTreeMap<i32, usize> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population = population();
+
+        histogram((0..1000).map(|_| selection.select(&mut rng, &population)))
+    }
+
+    #[test]
+    fn linear() {
+        let actual_histogram = select(RankSelection::linear(1.5));
+
+        let expected_histogram = maplit::btreemap! {
+            // fitness => how many times this fitness has been chosen
+            1 => 134,
+            2 => 193,
+            3 => 310,
+            400 => 363,
+        };
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    #[test]
+    fn exponential() {
+        let actual_histogram = select(RankSelection::exponential(0.5));
+
+        let expected_histogram = maplit::btreemap! {
+            // fitness => how many times this fitness has been chosen
+            1 => 72,
+            2 => 130,
+            3 => 273,
+            400 => 525,
+        };
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    #[test]
+    fn select_many() {
+        let selection = RankSelection::linear(1.5);
+        let population = population();
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        // Drawing from a single ranking is the same as ranking for each draw
+        let actual_histogram = histogram(selection.select_many(&mut rng, &population, 1000));
+
+        assert_eq!(actual_histogram, select(selection));
+    }
+}
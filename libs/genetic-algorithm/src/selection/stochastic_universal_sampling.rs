@@ -0,0 +1,118 @@
+use crate::*;
+
+/// Fitness-proportionate selection that spins the wheel once and picks all
+/// individuals with evenly spaced pointers, so the number of copies of each
+/// individual never strays far from its expected value.
+///
+/// Selecting a single individual is equivalent to the roulette wheel;
+/// `GeneticAlgorithm` samples its whole mating pool together.
+#[derive(Clone, Debug, Default)]
+pub struct StochasticUniversalSampling;
+
+impl SelectionMethod for StochasticUniversalSampling {
+    fn select<'a, I, G>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+        where
+            I: Individual<G>,
+    {
+        self.select_many(rng, population, 1).remove(0)
+    }
+
+    fn select_many<'a, I, G>(
+        &self,
+        rng: &mut dyn RngCore,
+        population: &'a [I],
+        count: usize,
+    ) -> Vec<&'a I>
+        where
+            I: Individual<G>,
+    {
+        assert!(!population.is_empty(), "got an empty population");
+
+        if count == 0 {
+            return Vec::new();
+        }
+
+        let fitnesses: Vec<_> = population
+            .iter()
+            .map(|individual| individual.fitness().max(0.00001))
+            .collect();
+
+        let step = fitnesses.iter().sum::<f32>() / (count as f32);
+        let mut pointer = rng.gen_range(0.0..step);
+
+        let mut selected = Vec::with_capacity(count);
+        let mut cumulative = 0.0;
+
+        for (individual, fitness) in population.iter().zip(fitnesses) {
+            cumulative += fitness;
+
+            while pointer < cumulative && selected.len() < count {
+                selected.push(individual);
+                pointer += step;
+            }
+        }
+
+        // Rounding errors might leave the last pointer(s) just past the end of
+        // the wheel
+        while selected.len() < count {
+            selected.push(&population[population.len() - 1]);
+        }
+
+        selected.shuffle(rng);
+        selected
+    }
+
+    fn selects_in_batches(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let population = vec![
+            TestIndividual::new(2.0),
+            TestIndividual::new(1.0),
+            TestIndividual::new(4.0),
+            TestIndividual::new(3.0),
+        ];
+
+        let actual_histogram = StochasticUniversalSampling
+            .select_many(&mut rng, &population, 1000)
+            .into_iter()
+            .fold(BTreeMap::default(), |mut histogram, individual| {
+                *histogram.entry(individual.fitness() as i32).or_default() += 1;
+                histogram
+            });
+
+        let expected_histogram = maplit::btreemap! {
+            // fitness => how many times this fitness has been chosen
+            1 => 100,
+            2 => 200,
+            3 => 300,
+            4 => 400,
+        };
+
+        // Contrary to the roulette wheel, the histogram matches the fitness
+        // proportions exactly
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    #[test]
+    fn try_select_many() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let population: Vec<TestIndividual> = Vec::new();
+
+        assert!(StochasticUniversalSampling
+            .try_select_many(&mut rng, &population, 2)
+            .is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use crate::*;
+use std::cmp::Ordering;
+
+#[derive(Clone, Debug)]
+pub struct TournamentSelection {
+    size: usize,
+    probability: f32,
+}
+
+impl TournamentSelection {
+    /// Creates a tournament of `size` randomly drawn contestants, where the
+    /// best contestant wins with `probability`, the second best with
+    /// `probability * (1 - probability)` and so on.
+    pub fn new(size: usize, probability: f32) -> Self {
+        Self::try_new(size, probability).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    pub fn try_new(size: usize, probability: f32) -> Result<Self, GaError> {
+        GaError::check_positive("tournament size", size as f32)?;
+        GaError::check_probability("tournament probability", probability)?;
+
+        Ok(Self { size, probability })
+    }
+}
+
+impl SelectionMethod for TournamentSelection {
+    fn select<'a, I, G>(&self, rng: &mut dyn RngCore, population: &'a [I]) -> &'a I
+        where
+            I: Individual<G>,
+    {
+        let mut contestants: Vec<_> = (0..self.size)
+            .map(|_| population.choose(rng).expect("got an empty population"))
+            .collect();
+
+        contestants.sort_by(|a, b| {
+            b.fitness()
+                .partial_cmp(&a.fitness())
+                .unwrap_or(Ordering::Equal)
+        });
+
+        let (last, rest) = contestants.split_last().unwrap();
+
+        rest.iter()
+            .find(|_| rng.gen_bool(self.probability as _))
+            .unwrap_or(last)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+    use std::collections::BTreeMap;
+
+    fn histogram(
+        selection: TournamentSelection,
+        population: &[TestIndividual],
+    ) -> BTreeMap<i32, usize> {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        (0..1000)
+            .map(|_| selection.select(&mut rng, population))
+            .fold(BTreeMap::default(), |mut histogram, individual| {
+                *histogram.entry(individual.fitness() as i32).or_default() += 1;
+                histogram
+            })
+    }
+
+    #[test]
+    fn test() {
+        let population = vec![
+            TestIndividual::new(2.0),
+            TestIndividual::new(1.0),
+            TestIndividual::new(4.0),
+            TestIndividual::new(3.0),
+        ];
+
+        let actual_histogram = histogram(TournamentSelection::new(2, 1.0), &population);
+
+        let expected_histogram = maplit::btreemap! {
+            // fitness => how many times this fitness has been chosen
+            1 => 66,
+            2 => 179,
+            3 => 299,
+            4 => 456,
+        };
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+
+    #[test]
+    fn negative_fitness() {
+        let population = vec![
+            TestIndividual::new(-2.0),
+            TestIndividual::new(-1.0),
+            TestIndividual::new(-4.0),
+            TestIndividual::new(-3.0),
+        ];
+
+        let actual_histogram = histogram(TournamentSelection::new(3, 0.75), &population);
+
+        let expected_histogram = maplit::btreemap! {
+            // fitness => how many times this fitness has been chosen
+            -4 => 82,
+            -3 => 138,
+            -2 => 288,
+            -1 => 492,
+        };
+
+        assert_eq!(actual_histogram, expected_histogram);
+    }
+}
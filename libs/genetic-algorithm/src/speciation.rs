@@ -0,0 +1,129 @@
+use crate::*;
+
+/// Clusters the population into species of genetically similar individuals
+/// and shares scores among the members of each species, so that a single
+/// dominant strategy can't take over the entire population.
+pub(crate) struct Speciation {
+    threshold: f32,
+}
+
+impl Speciation {
+    pub(crate) fn try_new(threshold: f32) -> Result<Self, GaError> {
+        GaError::check_positive("speciation threshold", threshold)?;
+
+        Ok(Self { threshold })
+    }
+
+    /// Returns the species of each individual; an individual joins the first
+    /// species whose founder lies closer than the threshold, or founds a new
+    /// one.
+    pub(crate) fn species<I, G>(&self, population: &[I]) -> Vec<usize>
+        where
+            I: Individual<G>,
+            G: GeneDistance,
+    {
+        let mut founders: Vec<&Chromosome<G>> = Vec::new();
+
+        population
+            .iter()
+            .map(|individual| {
+                let chromosome = individual.chromosome();
+
+                founders
+                    .iter()
+                    .position(|founder| founder.distance(chromosome) < self.threshold)
+                    .unwrap_or_else(|| {
+                        founders.push(chromosome);
+                        founders.len() - 1
+                    })
+            })
+            .collect()
+    }
+
+    /// Divides each individual's score by the size of its species; returns
+    /// the number of species, too.
+    ///
+    /// Negative scores get shifted up to zero first, so that being in a
+    /// crowded species never makes them better.
+    pub(crate) fn share<I, G>(&self, population: &[I], scores: &mut [f32]) -> usize
+        where
+            I: Individual<G>,
+            G: GeneDistance,
+    {
+        let species = self.species(population);
+        let species_count = species.iter().max().map_or(0, |max| max + 1);
+        let mut species_sizes = vec![0; species_count];
+
+        for &species in &species {
+            species_sizes[species] += 1;
+        }
+
+        let min_score = scores.iter().copied().fold(0.0, f32::min);
+
+        for (score, species) in scores.iter_mut().zip(species) {
+            *score = (*score - min_score) / (species_sizes[species] as f32);
+        }
+
+        species_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn individual(genes: &[f32]) -> TestIndividual {
+        TestIndividual::create(genes.iter().cloned().collect())
+    }
+
+    fn population() -> Vec<TestIndividual> {
+        vec![
+            individual(&[0.0, 0.0, 1.0]),
+            individual(&[5.0, 5.0, 5.0]),
+            individual(&[0.0, 0.5, 1.0]),
+            individual(&[0.5, 0.0, 1.0]),
+            individual(&[5.0, 5.0, 6.0]),
+        ]
+    }
+
+    #[test]
+    fn species() {
+        let species = Speciation::try_new(0.5).unwrap().species(&population());
+
+        assert_eq!(species, vec![0, 1, 0, 0, 1]);
+    }
+
+    #[test]
+    fn species_exclude_threshold() {
+        let population = vec![individual(&[0.0, 0.0, 1.0]), individual(&[0.0, 0.5, 1.0])];
+        let threshold = population[0].chromosome().distance(population[1].chromosome());
+
+        let species = Speciation::try_new(threshold).unwrap().species(&population);
+
+        assert_eq!(species, vec![0, 1]);
+    }
+
+    #[test]
+    fn share() {
+        let population = population();
+        let mut actual: Vec<_> = population.iter().map(|i| i.fitness()).collect();
+        let species_count = Speciation::try_new(0.5).unwrap().share(&population, &mut actual);
+
+        let expected = vec![1.0 / 3.0, 15.0 / 2.0, 1.5 / 3.0, 1.5 / 3.0, 16.0 / 2.0];
+
+        assert_eq!(species_count, 2);
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+
+    #[test]
+    fn share_negative_scores() {
+        let population = population();
+        let mut actual = vec![-2.0, -4.0, -2.0, -2.0, -1.0];
+
+        Speciation::try_new(0.5).unwrap().share(&population, &mut actual);
+
+        let expected = vec![2.0 / 3.0, 0.0 / 2.0, 2.0 / 3.0, 2.0 / 3.0, 3.0 / 2.0];
+
+        approx::assert_relative_eq!(actual.as_slice(), expected.as_slice());
+    }
+}
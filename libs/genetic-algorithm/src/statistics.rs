@@ -1,19 +1,34 @@
 use crate::*;
+use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 
-#[derive(Clone, Debug)]
-pub struct Statistics {
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Statistics<G = f32> {
     max_fitness: f32,
     min_fitness: f32,
     avg_fitness: f32,
     median_fitness: f32,
+    std_dev_fitness: f32,
+    percentiles: Vec<(f32, f32)>,
+    diversity: f32,
+    best_chromosome: Chromosome<G>,
+    species: Option<usize>,
+    mutation_rates: Option<MutationRates>,
 }
 
-impl Statistics {
-    pub(crate) fn new<I>(population: &[I]) -> Self
+impl<G> Statistics<G> {
+    /// Computes statistics of given population, including fitness at given
+    /// `percentiles` (each in `0.0..=100.0`).
+    pub fn new<'a, I>(
+        population: impl IntoIterator<Item = &'a I>,
+        percentiles: &[f32],
+    ) -> Self
         where
-            I: Individual,
+            I: Individual<G> + 'a,
+            G: Clone + GeneDistance,
     {
+        let population: Vec<_> = population.into_iter().collect();
+
         assert!(!population.is_empty());
 
         let len = population.len();
@@ -28,13 +43,47 @@ impl Statistics {
         let max_fitness = fitnesses[len - 1];
         let avg_fitness = fitnesses.iter().sum::<f32>() / (len as f32);
 
-        let median_fitness = if len % 2 == 0 {
+        let median_fitness = if len.is_multiple_of(2) {
             (fitnesses[len / 2 - 1] + fitnesses[len / 2]) / 2.0
         } else {
             fitnesses[len / 2]
         };
 
-        Self { min_fitness, max_fitness, avg_fitness, median_fitness }
+        let std_dev_fitness = (fitnesses
+            .iter()
+            .map(|fitness| (fitness - avg_fitness).powi(2))
+            .sum::<f32>()
+            / (len as f32))
+            .sqrt();
+
+        let percentiles = percentiles
+            .iter()
+            .map(|&percentile| (percentile, Self::percentile_of(&fitnesses, percentile)))
+            .collect();
+
+        let best_chromosome = population
+            .iter()
+            .max_by(|a, b| {
+                a.fitness()
+                    .partial_cmp(&b.fitness())
+                    .unwrap_or(Ordering::Equal)
+            })
+            .unwrap()
+            .chromosome()
+            .clone();
+
+        Self {
+            min_fitness,
+            max_fitness,
+            avg_fitness,
+            median_fitness,
+            std_dev_fitness,
+            percentiles,
+            diversity: Self::diversity_of(&population),
+            best_chromosome,
+            species: None,
+            mutation_rates: None,
+        }
     }
 
     pub fn min_fitness(&self) -> f32 {
@@ -52,6 +101,97 @@ impl Statistics {
     pub fn median_fitness(&self) -> f32 {
         self.median_fitness
     }
+
+    pub fn std_dev_fitness(&self) -> f32 {
+        self.std_dev_fitness
+    }
+
+    /// Returns `(percentile, fitness)` pairs for the percentiles passed to
+    /// `GeneticAlgorithm::with_percentiles()`.
+    pub fn percentiles(&self) -> &[(f32, f32)] {
+        &self.percentiles
+    }
+
+    /// Returns fitness at given percentile, if it has been computed.
+    pub fn percentile(&self, percentile: f32) -> Option<f32> {
+        self.percentiles
+            .iter()
+            .find(|(p, _)| *p == percentile)
+            .map(|(_, fitness)| *fitness)
+    }
+
+    /// Returns the mean distance between every two chromosomes (see
+    /// `Chromosome::distance()`); drops towards zero as the population
+    /// converges.
+    pub fn diversity(&self) -> f32 {
+        self.diversity
+    }
+
+    pub fn best_chromosome(&self) -> &Chromosome<G> {
+        &self.best_chromosome
+    }
+
+    /// Returns the number of species the population has been clustered into,
+    /// if speciation is enabled.
+    pub fn species(&self) -> Option<usize> {
+        self.species
+    }
+
+    /// Returns the rates the next generation has been mutated with, if they
+    /// are adapted over time.
+    pub fn mutation_rates(&self) -> Option<MutationRates> {
+        self.mutation_rates
+    }
+
+    pub fn with_species(mut self, species: usize) -> Self {
+        self.species = Some(species);
+        self
+    }
+
+    /// Overrides the diversity, for populations whose chromosomes don't tell
+    /// them apart (e.g. ones evolved by a structure-changing method).
+    pub fn with_diversity(mut self, diversity: f32) -> Self {
+        self.diversity = diversity;
+        self
+    }
+
+    pub(crate) fn with_mutation_rates(mut self, mutation_rates: Option<MutationRates>) -> Self {
+        self.mutation_rates = mutation_rates;
+        self
+    }
+
+    /// Interpolates linearly between the two closest ranks.
+    pub(crate) fn percentile_of(fitnesses: &[f32], percentile: f32) -> f32 {
+        assert!((0.0..=100.0).contains(&percentile));
+
+        let rank = percentile / 100.0 * ((fitnesses.len() - 1) as f32);
+        let lo = rank.floor() as usize;
+        let hi = rank.ceil() as usize;
+
+        fitnesses[lo] + (fitnesses[hi] - fitnesses[lo]) * (rank - lo as f32)
+    }
+
+    pub(crate) fn diversity_of<I>(population: &[&I]) -> f32
+        where
+            I: Individual<G>,
+            G: GeneDistance,
+    {
+        let mut sum = 0.0;
+        let mut pairs = 0;
+
+        for (idx, a) in population.iter().enumerate() {
+            for b in &population[idx + 1..] {
+                sum += a.chromosome().distance(b.chromosome());
+                pairs += 1;
+            }
+        }
+
+        if pairs == 0 {
+            0.0
+        } else {
+            sum / (pairs as f32)
+        }
+    }
 }
 
 
@@ -59,32 +199,52 @@ impl Statistics {
 mod tests {
     use super::*;
 
+    fn individual(fitness: f32) -> TestIndividual {
+        TestIndividual::create(vec![fitness].into_iter().collect())
+    }
+
+    fn statistics(population: &[TestIndividual]) -> Statistics {
+        Statistics::new(population, &[25.0, 90.0])
+    }
+
     #[test]
     fn test_even() {
-        let stats = Statistics::new(&[
-            TestIndividual::new(30.0),
-            TestIndividual::new(10.0),
-            TestIndividual::new(20.0),
-            TestIndividual::new(40.0),
+        let stats = statistics(&[
+            individual(30.0),
+            individual(10.0),
+            individual(20.0),
+            individual(40.0),
         ]);
 
         approx::assert_relative_eq!(stats.min_fitness(), 10.0);
         approx::assert_relative_eq!(stats.max_fitness(), 40.0);
         approx::assert_relative_eq!(stats.avg_fitness(), (10.0 + 20.0 + 30.0 + 40.0) / 4.0);
         approx::assert_relative_eq!(stats.median_fitness(), (20.0 + 30.0) / 2.0);
+        approx::assert_relative_eq!(stats.std_dev_fitness(), 125.0_f32.sqrt());
+        approx::assert_relative_eq!(stats.percentile(25.0).unwrap(), 17.5);
+        approx::assert_relative_eq!(stats.percentile(90.0).unwrap(), 37.0);
+        approx::assert_relative_eq!(
+            stats.diversity(),
+            (10.0 + 20.0 + 30.0 + 10.0 + 20.0 + 10.0) / 6.0
+        );
+        assert_eq!(stats.percentile(50.0), None);
+        assert_eq!(stats.best_chromosome(), &vec![40.0].into_iter().collect());
     }
 
     #[test]
     fn test_odd() {
-        let stats = Statistics::new(&[
-            TestIndividual::new(30.0),
-            TestIndividual::new(20.0),
-            TestIndividual::new(40.0),
+        let stats = statistics(&[
+            individual(30.0),
+            individual(20.0),
+            individual(40.0),
         ]);
 
         approx::assert_relative_eq!(stats.min_fitness(), 20.0);
         approx::assert_relative_eq!(stats.max_fitness(), 40.0);
         approx::assert_relative_eq!(stats.avg_fitness(), (20.0 + 30.0 + 40.0) / 3.0);
         approx::assert_relative_eq!(stats.median_fitness(), 30.0);
+        approx::assert_relative_eq!(stats.std_dev_fitness(), (200.0_f32 / 3.0).sqrt());
+        approx::assert_relative_eq!(stats.percentile(25.0).unwrap(), 25.0);
+        approx::assert_relative_eq!(stats.percentile(90.0).unwrap(), 38.0);
     }
 }
\ No newline at end of file
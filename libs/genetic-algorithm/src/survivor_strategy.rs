@@ -0,0 +1,16 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SurvivorStrategy {
+    /// Each generation is entirely replaced by children (apart from elites).
+    #[default]
+    Generational,
+
+    /// (mu + lambda): the `mu` best individuals of the evaluated population -
+    /// no matter whether they were parents or children - survive unchanged,
+    /// and the remaining `lambda = population.len() - mu` slots are filled
+    /// with their children.
+    ///
+    /// As fitness gets computed after `evolve()`, survivors compete with
+    /// children one generation later, once both have been evaluated. Parents
+    /// are drawn uniformly from the survivors, as in evolution strategies.
+    MuPlusLambda { mu: usize },
+}
@@ -0,0 +1,101 @@
+use std::ops::RangeInclusive;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum Activation {
+    #[default]
+    Relu,
+
+    /// Like ReLU, but lets negative values through scaled by given slope.
+    LeakyRelu(f32),
+
+    Sigmoid,
+    Tanh,
+    Identity,
+
+    /// `x / (1 + |x|)` - like tanh, but approaches its bounds more slowly.
+    Softsign,
+
+    /// `1` for positive values, `0` otherwise.
+    Step,
+}
+
+impl Activation {
+    pub fn apply(&self, x: f32) -> f32 {
+        match self {
+            Self::Relu => x.max(0.0),
+            Self::LeakyRelu(slope) => {
+                if x >= 0.0 {
+                    x
+                } else {
+                    slope * x
+                }
+            }
+            Self::Sigmoid => 1.0 / (1.0 + (-x).exp()),
+            Self::Tanh => x.tanh(),
+            Self::Identity => x,
+            Self::Softsign => x / (1.0 + x.abs()),
+            Self::Step => {
+                if x > 0.0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+
+    /// Returns the range of values this activation can produce, if it's
+    /// bounded.
+    pub fn bounds(&self) -> Option<RangeInclusive<f32>> {
+        match self {
+            Self::Sigmoid | Self::Step => Some(0.0..=1.0),
+            Self::Tanh | Self::Softsign => Some(-1.0..=1.0),
+            Self::Relu | Self::LeakyRelu(_) | Self::Identity => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn outputs(activation: Activation) -> Vec<f32> {
+        [-2.0, 0.0, 2.0]
+            .iter()
+            .map(|&x| activation.apply(x))
+            .collect()
+    }
+
+    #[test]
+    fn apply() {
+        assert_relative_eq!(outputs(Activation::Relu).as_slice(), [0.0, 0.0, 2.0].as_slice());
+
+        assert_relative_eq!(
+            outputs(Activation::LeakyRelu(0.1)).as_slice(),
+            [-0.2, 0.0, 2.0].as_slice()
+        );
+
+        assert_relative_eq!(
+            outputs(Activation::Sigmoid).as_slice(),
+            [0.11920292, 0.5, 0.8807971].as_slice()
+        );
+
+        assert_relative_eq!(
+            outputs(Activation::Tanh).as_slice(),
+            [-0.9640276, 0.0, 0.9640276].as_slice()
+        );
+
+        assert_relative_eq!(
+            outputs(Activation::Identity).as_slice(),
+            [-2.0, 0.0, 2.0].as_slice()
+        );
+
+        assert_relative_eq!(
+            outputs(Activation::Softsign).as_slice(),
+            [-0.6666667, 0.0, 0.6666667].as_slice()
+        );
+
+        assert_relative_eq!(outputs(Activation::Step).as_slice(), [0.0, 0.0, 1.0].as_slice());
+    }
+}
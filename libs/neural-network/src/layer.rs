@@ -2,7 +2,16 @@ use crate::*;
 
 #[derive(Clone, Debug)]
 pub struct Layer {
-    pub(crate) neurons: Vec<Neuron>,
+    /// Number of inputs of each neuron, outputs fed back by a recurrent
+    /// layer included.
+    pub(crate) inputs: usize,
+
+    /// Bias and weights of each neuron, one row per neuron - laid out the
+    /// same way `Network::weights()` returns them.
+    pub(crate) weights: Vec<f32>,
+
+    pub(crate) activation: Activation,
+    pub(crate) recurrent: bool,
 }
 
 impl Layer {
@@ -13,7 +22,33 @@ impl Layer {
             .iter()
             .all(|neuron| neuron.weights.len() == neurons[0].weights.len()));
 
-        Self { neurons }
+        let inputs = neurons[0].weights.len();
+
+        let weights = neurons
+            .into_iter()
+            .flat_map(|neuron| std::iter::once(neuron.bias).chain(neuron.weights))
+            .collect();
+
+        Self {
+            inputs,
+            weights,
+            activation: Activation::default(),
+            recurrent: false,
+        }
+    }
+
+    pub fn with_activation(mut self, activation: Activation) -> Self {
+        self.activation = activation;
+        self
+    }
+
+    /// Makes the layer feed its previous outputs back to its neurons, as
+    /// their last inputs; neurons must have room for them in their weights.
+    pub fn with_recurrence(mut self) -> Self {
+        assert!(self.inputs > self.outputs());
+
+        self.recurrent = true;
+        self
     }
 
 
@@ -38,14 +73,40 @@ impl Layer {
         Self::new(neurons)
     }
 
+    pub fn outputs(&self) -> usize {
+        self.weights.len() / (self.inputs + 1)
+    }
+
+    /// Writes this layer's response to `input` into `output`, without
+    /// allocating once `output` is large enough; `state` holds previous
+    /// outputs of a recurrent layer and is ignored otherwise.
+    pub fn propagate_into(&self, input: &[f32], state: &mut [f32], output: &mut Vec<f32>) {
+        let previous = if self.recurrent { &*state } else { &[] };
 
-    pub fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
-        let mut outputs = Vec::new();
-        for neuron in &self.neurons {
-            let output = neuron.propagate(&inputs);
-            outputs.push(output);
+        assert_eq!(input.len() + previous.len(), self.inputs);
+
+        output.clear();
+
+        for row in self.rows() {
+            let (bias, weights) = row.split_first().unwrap();
+            let (weights, recurrent_weights) = weights.split_at(input.len());
+
+            let sum = Self::dot(weights, input) + Self::dot(recurrent_weights, previous);
+
+            output.push(self.activation.apply(bias + sum));
+        }
+
+        if self.recurrent {
+            state.copy_from_slice(output);
         }
-        outputs
+    }
+
+    pub(crate) fn rows(&self) -> impl Iterator<Item = &[f32]> + '_ {
+        self.weights.chunks_exact(self.inputs + 1)
+    }
+
+    fn dot(weights: &[f32], values: &[f32]) -> f32 {
+        weights.iter().zip(values).map(|(weight, value)| weight * value).sum()
     }
 }
 
@@ -57,19 +118,22 @@ mod tests {
     use rand::SeedableRng;
     use rand_chacha::ChaCha8Rng;
 
+    fn propagate(layer: &Layer, inputs: &[f32]) -> Vec<f32> {
+        let mut output = Vec::new();
+
+        layer.propagate_into(inputs, &mut [], &mut output);
+        output
+    }
+
     #[test]
     fn random() {
         let mut rng = ChaCha8Rng::from_seed(Default::default());
         let layer = Layer::random(&mut rng, 3, 2);
 
-        let actual_biases: Vec<_> = layer.neurons.iter().map(|neuron| neuron.bias).collect();
+        let actual_biases: Vec<_> = layer.rows().map(|row| row[0]).collect();
         let expected_biases = vec![-0.6255188, 0.5238807];
 
-        let actual_weights: Vec<_> = layer
-            .neurons
-            .iter()
-            .map(|neuron| neuron.weights.as_slice())
-            .collect();
+        let actual_weights: Vec<_> = layer.rows().map(|row| &row[1..]).collect();
 
         let expected_weights: Vec<&[f32]> = vec![
             &[0.67383957, 0.8181262, 0.26284897],
@@ -80,20 +144,47 @@ mod tests {
         assert_relative_eq!(actual_weights.as_slice(), expected_weights.as_slice());
     }
 
-    #[test]
-    fn propagate() {
-        let neurons = (
-            Neuron::new(0.0, vec![0.1, 0.2, 0.3]),
-            Neuron::new(0.0, vec![0.4, 0.5, 0.6]),
-        );
+    mod propagate {
+        use super::*;
+
+        #[test]
+        fn returns_propagated_input() {
+            let layer = Layer::new(vec![
+                Neuron::new(0.1, vec![-0.3, 0.6, 0.9]),
+                Neuron::new(0.0, vec![0.4, 0.5, 0.6]),
+            ]);
+
+            let actual = propagate(&layer, &[0.5, -0.6, 0.7]);
+
+            let expected: [f32; 2] = [
+                0.1 + (0.5 * -0.3) + (-0.6 * 0.6) + (0.7 * 0.9),
+                (0.5 * 0.4) + (-0.6 * 0.5) + (0.7 * 0.6),
+            ];
+
+            assert_relative_eq!(actual.as_slice(), expected.map(|v| v.max(0.0)).as_slice());
+        }
+
+        #[test]
+        fn restricts_output() {
+            let layer = Layer::new(vec![Neuron::new(0.0, vec![0.5])]);
+
+            let actual: Vec<_> = [-1.0, -0.5, 0.0, 0.5, 1.0]
+                .iter()
+                .map(|&input| propagate(&layer, &[input])[0])
+                .collect();
+
+            assert_relative_eq!(actual.as_slice(), [0.0, 0.0, 0.0, 0.25, 0.5].as_slice());
+        }
 
-        let layer = Layer::new(vec![neurons.0.clone(), neurons.1.clone()]);
-        let inputs = &[-0.5, 0.0, 0.5];
+        #[test]
+        fn reuses_output() {
+            let layer = Layer::new(vec![Neuron::new(0.0, vec![1.0])]);
+            let mut output = vec![9.0; 4];
 
-        let actual = layer.propagate(inputs.to_vec());
-        let expected = vec![neurons.0.propagate(inputs), neurons.1.propagate(inputs)];
+            layer.propagate_into(&[2.0], &mut [], &mut output);
 
-        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+            assert_eq!(output, vec![2.0]);
+        }
     }
 
     #[test]
@@ -104,17 +195,34 @@ mod tests {
             &mut vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8].into_iter(),
         );
 
-        let actual_biases: Vec<_> = layer.neurons.iter().map(|neuron| neuron.bias).collect();
+        let actual_biases: Vec<_> = layer.rows().map(|row| row[0]).collect();
         let expected_biases = vec![0.1, 0.5];
 
-        let actual_weights: Vec<_> = layer
-            .neurons
-            .iter()
-            .map(|neuron| neuron.weights.as_slice())
-            .collect();
+        let actual_weights: Vec<_> = layer.rows().map(|row| &row[1..]).collect();
         let expected_weights: Vec<&[f32]> = vec![&[0.2, 0.3, 0.4], &[0.6, 0.7, 0.8]];
 
         assert_relative_eq!(actual_biases.as_slice(), expected_biases.as_slice());
         assert_relative_eq!(actual_weights.as_slice(), expected_weights.as_slice());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn recurrence() {
+        let layer = Layer::new(vec![Neuron::new(0.0, vec![1.0, 0.5])])
+            .with_activation(Activation::Identity)
+            .with_recurrence();
+
+        let mut state = [0.0];
+        let mut output = Vec::new();
+
+        // Each output is the input plus half of the previous output
+        let outputs: Vec<_> = [1.0, 1.0, 0.0]
+            .iter()
+            .map(|&input| {
+                layer.propagate_into(&[input], &mut state, &mut output);
+                output[0]
+            })
+            .collect();
+
+        assert_relative_eq!(outputs.as_slice(), [1.0, 1.5, 0.75].as_slice());
+    }
+}
@@ -1,5 +1,44 @@
+use crate::*;
 
 #[derive(Clone, Copy, Debug)]
 pub struct LayerTopology {
     pub neurons: usize,
-}
\ No newline at end of file
+
+    /// Activation of this layer's neurons; ignored for the input layer.
+    pub activation: Activation,
+
+    /// Whether this layer's neurons also see the layer's outputs from the
+    /// previous `Network::propagate()` call (Elman-style memory); ignored
+    /// for the input layer.
+    pub recurrent: bool,
+}
+
+impl LayerTopology {
+    pub fn new(neurons: usize) -> Self {
+        Self {
+            neurons,
+            activation: Activation::default(),
+            recurrent: false,
+        }
+    }
+
+    pub fn with_activation(mut self, activation: Activation) -> Self {
+        self.activation = activation;
+        self
+    }
+
+    pub fn with_recurrence(mut self) -> Self {
+        self.recurrent = true;
+        self
+    }
+
+    /// Returns the number of inputs each of this layer's neurons has, given
+    /// the previous layer.
+    pub(crate) fn inputs(&self, previous: &LayerTopology) -> usize {
+        if self.recurrent {
+            previous.neurons + self.neurons
+        } else {
+            previous.neurons
+        }
+    }
+}
@@ -1,22 +1,34 @@
+mod activation;
 mod layer;
 mod layer_topology;
+mod neat;
 mod neuron;
+mod scratch;
 
 
+pub use self::activation::*;
 use self::layer::*;
 pub use self::layer_topology::*;
+pub use self::neat::*;
 use self::neuron::*;
+pub use self::scratch::*;
 use rand::{Rng, RngCore};
 
 #[derive(Clone, Debug)]
 pub struct Network {
     layers: Vec<Layer>,
+
+    /// Used by `propagate()`, which keeps recurrent layers' memory here.
+    scratch: Scratch,
 }
 
 
 impl Network {
     pub(crate) fn new(layers: Vec<Layer>) -> Self {
-        Self { layers }
+        Self {
+            layers,
+            scratch: Scratch::default(),
+        }
     }
 
     pub fn random(rng: &mut dyn RngCore, layers: &[LayerTopology]) -> Self {
@@ -24,10 +36,13 @@ impl Network {
         let mut built_layers = Vec::new();
 
         for i in 0..(layers.len() - 1) {
-            let input_size = layers[i].neurons;
+            let input_size = layers[i + 1].inputs(&layers[i]);
             let output_size = layers[i + 1].neurons;
 
-            built_layers.push(Layer::random(rng, input_size, output_size));
+            built_layers.push(Self::build_layer(
+                Layer::random(rng, input_size, output_size),
+                &layers[i + 1],
+            ));
         }
         Self::new(built_layers)
     }
@@ -39,10 +54,13 @@ impl Network {
         let mut weights = weights.into_iter();
         let mut built_layers = Vec::new();
         for i in 0..(layers.len() - 1) {
-            let input_size = layers[i].neurons;
+            let input_size = layers[i + 1].inputs(&layers[i]);
             let output_size = layers[i + 1].neurons;
 
-            built_layers.push(Layer::from_weights(input_size, output_size, &mut weights));
+            built_layers.push(Self::build_layer(
+                Layer::from_weights(input_size, output_size, &mut weights),
+                &layers[i + 1],
+            ));
         }
         if weights.next().is_some() {
             panic!("Got too many weights!!");
@@ -50,25 +68,77 @@ impl Network {
 
         Self::new(built_layers)
     }
-    pub fn propagate(&self, mut inputs: Vec<f32>) -> Vec<f32> {
-        for layer in &self.layers {
-            inputs = layer.propagate(inputs);
+    /// Propagates given inputs through the network; recurrent layers
+    /// remember their outputs for the next call.
+    pub fn propagate(&mut self, inputs: Vec<f32>) -> Vec<f32> {
+        let mut scratch = std::mem::take(&mut self.scratch);
+        let outputs = self.propagate_into(&inputs, &mut scratch).to_vec();
+
+        self.scratch = scratch;
+        outputs
+    }
+
+    /// Like `propagate()`, but works in (and keeps recurrent layers' memory
+    /// in) given scratch, without allocating once it's been used with this
+    /// network before.
+    pub fn propagate_into<'a>(&self, input: &[f32], scratch: &'a mut Scratch) -> &'a [f32] {
+        scratch.prepare(&self.layers);
+
+        let Scratch {
+            values,
+            buffer,
+            states,
+        } = &mut *scratch;
+
+        values.clear();
+        values.extend_from_slice(input);
+
+        for (layer, state) in self.layers.iter().zip(states) {
+            layer.propagate_into(values, state, buffer);
+            std::mem::swap(values, buffer);
         }
-        inputs
+
+        &scratch.values
     }
+
     pub fn weights(&self) -> Vec<f32> {
-        let mut weights: Vec<f32> = Vec::new();
-
-        for layer in &self.layers {
-            for neuron in &layer.neurons {
-                weights.push(neuron.bias);
-                for weight in &neuron.weights {
-                    weights.push(*weight);
-                }
-            }
-        }
+        self.layers
+            .iter()
+            .flat_map(|layer| layer.weights.iter().copied())
+            .collect()
+    }
 
-        weights
+    /// Clears the memory of recurrent layers, as if the network has never
+    /// propagated anything; see `Scratch::reset_state()` for
+    /// `propagate_into()`.
+    pub fn reset_state(&mut self) {
+        self.scratch.reset_state();
+    }
+
+    /// Returns the activation of each layer, input layer excluded.
+    pub fn activations(&self) -> Vec<Activation> {
+        self.layers.iter().map(|layer| layer.activation).collect()
+    }
+
+    /// Returns the number of weights (bias included) of each neuron, in the
+    /// same order `weights()` lays them out.
+    pub fn neuron_layout(layers: &[LayerTopology]) -> Vec<usize> {
+        assert!(layers.len() > 1);
+
+        layers
+            .windows(2)
+            .flat_map(|layers| vec![layers[1].inputs(&layers[0]) + 1; layers[1].neurons])
+            .collect()
+    }
+
+    fn build_layer(layer: Layer, topology: &LayerTopology) -> Layer {
+        let layer = layer.with_activation(topology.activation);
+
+        if topology.recurrent {
+            layer.with_recurrence()
+        } else {
+            layer
+        }
     }
 }
 
@@ -87,40 +157,42 @@ mod tests {
         let network = Network::random(
             &mut rng,
             &[
-                LayerTopology { neurons: 3 },
-                LayerTopology { neurons: 2 },
-                LayerTopology { neurons: 1 },
+                LayerTopology::new(3),
+                LayerTopology::new(2),
+                LayerTopology::new(1),
             ],
         );
 
         assert_eq!(network.layers.len(), 2);
-        assert_eq!(network.layers[0].neurons.len(), 2);
+        assert_eq!(network.layers[0].outputs(), 2);
+
+        let rows: Vec<_> = network.layers[0].rows().collect();
 
-        assert_relative_eq!(network.layers[0].neurons[0].bias, -0.6255188);
+        assert_relative_eq!(rows[0][0], -0.6255188);
 
         assert_relative_eq!(
-            network.layers[0].neurons[0].weights.as_slice(),
+            &rows[0][1..],
             &[0.67383957, 0.8181262, 0.26284897].as_slice()
         );
 
-        assert_relative_eq!(network.layers[0].neurons[1].bias, 0.5238807);
+        assert_relative_eq!(rows[1][0], 0.5238807);
 
         assert_relative_eq!(
-            network.layers[0].neurons[1].weights.as_slice(),
+            &rows[1][1..],
             &[-0.5351684, 0.069369555, -0.7648182].as_slice()
         );
 
-        assert_eq!(network.layers[1].neurons.len(), 1);
+        assert_eq!(network.layers[1].outputs(), 1);
 
         assert_relative_eq!(
-            network.layers[1].neurons[0].weights.as_slice(),
+            &network.layers[1].weights[1..],
             &[-0.48879623, -0.19277143].as_slice()
         );
     }
 
     #[test]
     fn from_weights() {
-        let layers = &[LayerTopology { neurons: 3 }, LayerTopology { neurons: 2 }];
+        let layers = &[LayerTopology::new(3), LayerTopology::new(2)];
         let weights = vec![0.1, 0.2, 0.3, 0.4, 0.5, 0.6, 0.7, 0.8];
 
         let actual: Vec<_> = Network::from_weights(layers, weights.clone())
@@ -131,6 +203,29 @@ mod tests {
         assert_relative_eq!(actual.as_slice(), weights.as_slice());
     }
 
+    #[test]
+    fn from_weights_keeps_activations() {
+        let layers = &[
+            LayerTopology::new(3),
+            LayerTopology::new(2).with_activation(Activation::Tanh),
+            LayerTopology::new(1).with_activation(Activation::Sigmoid),
+        ];
+
+        let mut network = Network::random(&mut ChaCha8Rng::from_seed(Default::default()), layers);
+        let mut restored = Network::from_weights(layers, network.weights());
+
+        assert_eq!(restored.activations(), vec![Activation::Tanh, Activation::Sigmoid]);
+
+        assert_relative_eq!(
+            restored.propagate(vec![0.5, -0.5, 1.0]).as_slice(),
+            network.propagate(vec![0.5, -0.5, 1.0]).as_slice()
+        );
+
+        let output = restored.propagate(vec![0.5, -0.5, 1.0])[0];
+
+        assert!((0.0..=1.0).contains(&output));
+    }
+
     #[test]
     fn propagate() {
         let layers = (
@@ -140,12 +235,15 @@ mod tests {
             ]),
             Layer::new(vec![Neuron::new(0.0, vec![-0.5, 0.5])]),
         );
-        let network = Network::new(vec![layers.0.clone(), layers.1.clone()]);
+        let mut network = Network::new(vec![layers.0.clone(), layers.1.clone()]);
 
         let actual = network.propagate(vec![0.5, 0.6, 0.7]);
-        let expected = layers.1.propagate(layers.0.propagate(vec![0.5, 0.6, 0.7]));
+        let mut expected = (Vec::new(), Vec::new());
 
-        assert_relative_eq!(actual.as_slice(), expected.as_slice());
+        layers.0.propagate_into(&[0.5, 0.6, 0.7], &mut [], &mut expected.0);
+        layers.1.propagate_into(&expected.0, &mut [], &mut expected.1);
+
+        assert_relative_eq!(actual.as_slice(), expected.1.as_slice());
     }
 
     #[test]
@@ -160,4 +258,75 @@ mod tests {
 
         assert_relative_eq!(actual.as_slice(), expected.as_slice());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn neuron_layout() {
+        let layers = &[
+            LayerTopology::new(3),
+            LayerTopology::new(2),
+            LayerTopology::new(1),
+        ];
+
+        let network = Network::random(&mut ChaCha8Rng::from_seed(Default::default()), layers);
+        let layout = Network::neuron_layout(layers);
+
+        assert_eq!(layout, vec![4, 4, 3]);
+        assert_eq!(layout.iter().sum::<usize>(), network.weights().len());
+    }
+
+    #[test]
+    fn recurrence() {
+        let layers = &[
+            LayerTopology::new(2),
+            LayerTopology::new(3)
+                .with_activation(Activation::Tanh)
+                .with_recurrence(),
+            LayerTopology::new(1).with_activation(Activation::Tanh),
+        ];
+
+        let mut network = Network::random(&mut ChaCha8Rng::from_seed(Default::default()), layers);
+        let layout = Network::neuron_layout(layers);
+
+        assert_eq!(layout, vec![6, 6, 6, 4]);
+        assert_eq!(layout.iter().sum::<usize>(), network.weights().len());
+
+        let mut restored = Network::from_weights(layers, network.weights());
+        let first = network.propagate(vec![0.5, -0.5]);
+        let second = network.propagate(vec![0.5, -0.5]);
+
+        // The same inputs yield different outputs, since the hidden layer
+        // remembers what it's seen
+        assert!((first[0] - second[0]).abs() > 1e-6);
+
+        network.reset_state();
+
+        assert_relative_eq!(network.propagate(vec![0.5, -0.5]).as_slice(), first.as_slice());
+        assert_relative_eq!(restored.propagate(vec![0.5, -0.5]).as_slice(), first.as_slice());
+    }
+
+    #[test]
+    fn propagate_into() {
+        let layers = &[
+            LayerTopology::new(3),
+            LayerTopology::new(4).with_recurrence(),
+            LayerTopology::new(2).with_activation(Activation::Sigmoid),
+        ];
+
+        let mut network = Network::random(&mut ChaCha8Rng::from_seed(Default::default()), layers);
+        let mut scratch = Scratch::new();
+
+        for inputs in [[0.5, -0.5, 1.0], [0.1, 0.2, 0.3], [1.0, 1.0, -1.0]] {
+            let expected = network.propagate(inputs.to_vec());
+            let actual = network.propagate_into(&inputs, &mut scratch);
+
+            assert_relative_eq!(actual, expected.as_slice());
+        }
+
+        // Buffers have grown large enough for the whole network already
+        let capacity = (scratch.values.capacity(), scratch.buffer.capacity());
+
+        network.propagate_into(&[0.0, 0.0, 0.0], &mut scratch);
+
+        assert_eq!((scratch.values.capacity(), scratch.buffer.capacity()), capacity);
+    }
+}
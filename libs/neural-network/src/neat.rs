@@ -0,0 +1,14 @@
+//! NeuroEvolution of Augmenting Topologies: instead of tuning the weights of
+//! a fixed `Network`, evolves the structure of the network too - starting
+//! from inputs wired straight to outputs and growing hidden neurons and
+//! connections over time.
+
+mod genome;
+mod innovations;
+mod network;
+mod population;
+
+pub use self::genome::*;
+pub use self::innovations::*;
+pub use self::network::*;
+pub use self::population::*;
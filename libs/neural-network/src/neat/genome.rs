@@ -0,0 +1,611 @@
+use crate::*;
+use rand::seq::SliceRandom;
+
+/// Chance of a connection gene staying disabled when it's disabled in either
+/// parent.
+const DISABLED_INHERITANCE: f64 = 0.75;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeKind {
+    Input,
+    Hidden,
+    Output,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeGene {
+    pub id: usize,
+    pub kind: NodeKind,
+
+    /// Ignored for input nodes.
+    pub bias: f32,
+
+    /// Ignored for input nodes.
+    pub activation: Activation,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConnectionGene {
+    pub innovation: usize,
+    pub from: usize,
+    pub to: usize,
+    pub weight: f32,
+    pub enabled: bool,
+}
+
+/// Coefficients of the compatibility distance between two genomes:
+/// `excess * E / N + disjoint * D / N + weight * W`, where `E` and `D` are
+/// the numbers of excess and disjoint connection genes, `N` is the number of
+/// connection genes of the larger genome and `W` is the average weight
+/// difference of matching genes.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Compatibility {
+    pub excess: f32,
+    pub disjoint: f32,
+    pub weight: f32,
+}
+
+impl Default for Compatibility {
+    fn default() -> Self {
+        Self {
+            excess: 1.0,
+            disjoint: 1.0,
+            weight: 0.4,
+        }
+    }
+}
+
+/// Network encoded as a list of nodes and a list of connections between
+/// them, each connection identified by its innovation number (see
+/// `Innovations`).
+///
+/// Connections - enabled or not - always form a directed acyclic graph.
+#[derive(Clone, Debug)]
+pub struct NeatGenome {
+    nodes: Vec<NodeGene>,
+    connections: Vec<ConnectionGene>,
+}
+
+impl NeatGenome {
+    pub fn new(mut nodes: Vec<NodeGene>, mut connections: Vec<ConnectionGene>) -> Self {
+        nodes.sort_by_key(|node| node.id);
+        connections.sort_by_key(|connection| connection.innovation);
+
+        assert!(
+            nodes.windows(2).all(|nodes| nodes[0].id != nodes[1].id),
+            "got duplicated nodes"
+        );
+
+        assert!(
+            connections
+                .windows(2)
+                .all(|connections| connections[0].innovation != connections[1].innovation),
+            "got duplicated connections"
+        );
+
+        let genome = Self { nodes, connections };
+
+        for connection in &genome.connections {
+            assert!(genome.node(connection.from).is_some(), "got an unknown node");
+
+            let to = genome.node(connection.to).expect("got an unknown node");
+
+            assert!(to.kind != NodeKind::Input, "got a connection into an input");
+        }
+
+        assert!(genome.order().is_some(), "got a cycle");
+
+        genome
+    }
+
+    /// Creates a genome with each input connected to each output, with
+    /// random weights.
+    pub fn minimal(
+        rng: &mut dyn RngCore,
+        innovations: &mut Innovations,
+        inputs: usize,
+        outputs: usize,
+        activation: Activation,
+    ) -> Self {
+        assert!(inputs > 0);
+        assert!(outputs > 0);
+
+        innovations.reserve(inputs + outputs);
+
+        let inputs: Vec<_> = (0..inputs)
+            .map(|id| NodeGene {
+                id,
+                kind: NodeKind::Input,
+                bias: 0.0,
+                activation: Activation::Identity,
+            })
+            .collect();
+
+        let outputs: Vec<_> = (inputs.len()..(inputs.len() + outputs))
+            .map(|id| NodeGene {
+                id,
+                kind: NodeKind::Output,
+                bias: rng.gen_range(-1.0..=1.0),
+                activation,
+            })
+            .collect();
+
+        let mut connections = Vec::new();
+
+        for input in &inputs {
+            for output in &outputs {
+                connections.push(ConnectionGene {
+                    innovation: innovations.connection(input.id, output.id),
+                    from: input.id,
+                    to: output.id,
+                    weight: rng.gen_range(-1.0..=1.0),
+                    enabled: true,
+                });
+            }
+        }
+
+        Self::new([inputs, outputs].concat(), connections)
+    }
+
+    /// Returns the nodes, ordered by their ids.
+    pub fn nodes(&self) -> &[NodeGene] {
+        &self.nodes
+    }
+
+    /// Returns the connections, ordered by their innovation numbers.
+    pub fn connections(&self) -> &[ConnectionGene] {
+        &self.connections
+    }
+
+    pub fn inputs(&self) -> usize {
+        self.count(NodeKind::Input)
+    }
+
+    pub fn outputs(&self) -> usize {
+        self.count(NodeKind::Output)
+    }
+
+    /// Nudges each weight and bias with probability `chance` by up to
+    /// `coeff`, like `UniformMutation` does for chromosomes.
+    pub fn mutate_weights(&mut self, rng: &mut dyn RngCore, chance: f32, coeff: f32) {
+        let biases = self
+            .nodes
+            .iter_mut()
+            .filter(|node| node.kind != NodeKind::Input)
+            .map(|node| &mut node.bias);
+
+        let weights = self.connections.iter_mut().map(|connection| &mut connection.weight);
+
+        for gene in biases.chain(weights) {
+            let sign = if rng.gen_bool(0.5) { -1.0 } else { 1.0 };
+
+            if rng.gen_bool(chance as _) {
+                *gene += sign * coeff * rng.gen::<f32>();
+            }
+        }
+    }
+
+    /// Connects a random pair of nodes that aren't connected yet, unless
+    /// every such connection would introduce a cycle; returns whether a
+    /// connection has been added.
+    pub fn mutate_add_connection(
+        &mut self,
+        rng: &mut dyn RngCore,
+        innovations: &mut Innovations,
+    ) -> bool {
+        let mut candidates = Vec::new();
+
+        for from in &self.nodes {
+            for to in &self.nodes {
+                if to.kind == NodeKind::Input
+                    || from.id == to.id
+                    || self.connected(from.id, to.id)
+                    || self.reaches(to.id, from.id)
+                {
+                    continue;
+                }
+
+                candidates.push((from.id, to.id));
+            }
+        }
+
+        let Some(&(from, to)) = candidates.choose(rng) else {
+            return false;
+        };
+
+        self.insert(ConnectionGene {
+            innovation: innovations.connection(from, to),
+            from,
+            to,
+            weight: rng.gen_range(-1.0..=1.0),
+            enabled: true,
+        });
+
+        true
+    }
+
+    /// Splits a random enabled connection in two with a new hidden node: the
+    /// incoming half gets a weight of 1 and the outgoing one the old weight,
+    /// so that the network behaves roughly the same; returns whether a node
+    /// has been added.
+    pub fn mutate_add_node(
+        &mut self,
+        rng: &mut dyn RngCore,
+        innovations: &mut Innovations,
+        activation: Activation,
+    ) -> bool {
+        let enabled: Vec<_> = (0..self.connections.len())
+            .filter(|&idx| self.connections[idx].enabled)
+            .collect();
+
+        let Some(&idx) = enabled.choose(rng) else {
+            return false;
+        };
+
+        self.connections[idx].enabled = false;
+
+        let ConnectionGene {
+            innovation,
+            from,
+            to,
+            weight,
+            ..
+        } = self.connections[idx].clone();
+
+        let mut node = innovations.split(innovation);
+
+        // This genome has already split that connection once (and got it
+        // re-enabled through crossover)
+        if self.node(node).is_some() {
+            node = innovations.node();
+        }
+
+        self.nodes.push(NodeGene {
+            id: node,
+            kind: NodeKind::Hidden,
+            bias: 0.0,
+            activation,
+        });
+
+        self.nodes.sort_by_key(|node| node.id);
+
+        self.insert(ConnectionGene {
+            innovation: innovations.connection(from, node),
+            from,
+            to: node,
+            weight: 1.0,
+            enabled: true,
+        });
+
+        self.insert(ConnectionGene {
+            innovation: innovations.connection(node, to),
+            from: node,
+            to,
+            weight,
+            enabled: true,
+        });
+
+        true
+    }
+
+    /// Breeds a child of two genomes: matching genes (with the same id or
+    /// innovation number) get inherited from either parent at random, while
+    /// disjoint and excess ones come from the fitter parent only - so the
+    /// child has the same structure as the fitter parent.
+    pub fn crossover(rng: &mut dyn RngCore, fitter: &Self, other: &Self) -> Self {
+        let nodes = fitter
+            .nodes
+            .iter()
+            .map(|node| match other.node(node.id) {
+                Some(other_node) if rng.gen_bool(0.5) => other_node.clone(),
+                _ => node.clone(),
+            })
+            .collect();
+
+        let connections = fitter
+            .connections
+            .iter()
+            .map(|connection| match other.connection(connection.innovation) {
+                Some(other_connection) => {
+                    let mut child = if rng.gen_bool(0.5) {
+                        connection.clone()
+                    } else {
+                        other_connection.clone()
+                    };
+
+                    child.enabled = if connection.enabled && other_connection.enabled {
+                        true
+                    } else {
+                        !rng.gen_bool(DISABLED_INHERITANCE)
+                    };
+
+                    child
+                }
+
+                None => connection.clone(),
+            })
+            .collect();
+
+        Self { nodes, connections }
+    }
+
+    /// Returns how structurally different two genomes are; used to tell
+    /// species apart.
+    pub fn distance(&self, other: &Self, compatibility: &Compatibility) -> f32 {
+        let mut excess = 0;
+        let mut disjoint = 0;
+        let mut matching = 0;
+        let mut weight_difference = 0.0;
+
+        for (genome, other_genome) in [(self, other), (other, self)] {
+            let other_max = other_genome.connections.last().map(|c| c.innovation);
+
+            for connection in &genome.connections {
+                match other_genome.connection(connection.innovation) {
+                    Some(other_connection) => {
+                        matching += 1;
+                        weight_difference += (connection.weight - other_connection.weight).abs();
+                    }
+
+                    None => match other_max {
+                        Some(max) if connection.innovation < max => disjoint += 1,
+                        _ => excess += 1,
+                    },
+                }
+            }
+        }
+
+        // Each matching pair has been visited twice
+        let weight_difference = if matching > 0 {
+            weight_difference / (matching as f32)
+        } else {
+            0.0
+        };
+
+        let n = self.connections.len().max(other.connections.len()).max(1) as f32;
+
+        compatibility.excess * (excess as f32) / n
+            + compatibility.disjoint * (disjoint as f32) / n
+            + compatibility.weight * weight_difference
+    }
+
+    /// Returns positions of all the nodes, ordered so that each node comes
+    /// after every node it's connected from; `None` if there's a cycle.
+    pub(crate) fn order(&self) -> Option<Vec<usize>> {
+        let mut incoming = vec![0; self.nodes.len()];
+
+        for connection in &self.connections {
+            incoming[self.position(connection.to)] += 1;
+        }
+
+        let mut ready: Vec<_> = (0..self.nodes.len())
+            .filter(|&idx| incoming[idx] == 0)
+            .rev()
+            .collect();
+
+        let mut order = Vec::new();
+
+        while let Some(idx) = ready.pop() {
+            order.push(idx);
+
+            for connection in &self.connections {
+                if connection.from == self.nodes[idx].id {
+                    let to = self.position(connection.to);
+
+                    incoming[to] -= 1;
+
+                    if incoming[to] == 0 {
+                        ready.push(to);
+                    }
+                }
+            }
+        }
+
+        (order.len() == self.nodes.len()).then_some(order)
+    }
+
+    pub(crate) fn position(&self, id: usize) -> usize {
+        self.nodes
+            .binary_search_by_key(&id, |node| node.id)
+            .expect("got an unknown node")
+    }
+
+    fn node(&self, id: usize) -> Option<&NodeGene> {
+        self.nodes
+            .binary_search_by_key(&id, |node| node.id)
+            .ok()
+            .map(|idx| &self.nodes[idx])
+    }
+
+    fn connection(&self, innovation: usize) -> Option<&ConnectionGene> {
+        self.connections
+            .binary_search_by_key(&innovation, |connection| connection.innovation)
+            .ok()
+            .map(|idx| &self.connections[idx])
+    }
+
+    fn count(&self, kind: NodeKind) -> usize {
+        self.nodes.iter().filter(|node| node.kind == kind).count()
+    }
+
+    fn connected(&self, from: usize, to: usize) -> bool {
+        self.connections
+            .iter()
+            .any(|connection| connection.from == from && connection.to == to)
+    }
+
+    /// Returns whether there's a path from one node to another, following
+    /// both enabled and disabled connections.
+    fn reaches(&self, from: usize, to: usize) -> bool {
+        let mut pending = vec![from];
+        let mut visited = vec![false; self.nodes.len()];
+
+        while let Some(id) = pending.pop() {
+            if id == to {
+                return true;
+            }
+
+            let idx = self.position(id);
+
+            if visited[idx] {
+                continue;
+            }
+
+            visited[idx] = true;
+
+            pending.extend(
+                self.connections
+                    .iter()
+                    .filter(|connection| connection.from == id)
+                    .map(|connection| connection.to),
+            );
+        }
+
+        false
+    }
+
+    fn insert(&mut self, connection: ConnectionGene) {
+        self.connections.push(connection);
+        self.connections.sort_by_key(|connection| connection.innovation);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    fn minimal(rng: &mut dyn RngCore, innovations: &mut Innovations) -> NeatGenome {
+        NeatGenome::minimal(rng, innovations, 2, 1, Activation::Identity)
+    }
+
+    #[test]
+    fn shares_innovations() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut innovations = Innovations::new();
+
+        let mut a = minimal(&mut rng, &mut innovations);
+        let mut b = minimal(&mut rng, &mut innovations);
+
+        assert_eq!((a.inputs(), a.outputs()), (2, 1));
+        assert_eq!(a.connections().len(), 2);
+
+        // Splitting the same connection yields the same genes in both
+        let split = |genome: &mut NeatGenome, innovations: &mut Innovations| {
+            for connection in &mut genome.connections[1..] {
+                connection.enabled = false;
+            }
+
+            let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+            genome.mutate_add_node(&mut rng, innovations, Activation::Identity)
+        };
+
+        assert!(split(&mut a, &mut innovations));
+        assert!(split(&mut b, &mut innovations));
+
+        let genes = |genome: &NeatGenome| -> Vec<_> {
+            genome
+                .connections()
+                .iter()
+                .map(|c| (c.innovation, c.from, c.to))
+                .collect()
+        };
+
+        assert_eq!(genes(&a), vec![(0, 0, 2), (1, 1, 2), (2, 0, 3), (3, 3, 2)]);
+        assert_eq!(genes(&a), genes(&b));
+        assert_eq!(a.nodes().last().unwrap().kind, NodeKind::Hidden);
+    }
+
+    #[test]
+    fn add_node_keeps_behaviour() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut innovations = Innovations::new();
+        let mut genome = minimal(&mut rng, &mut innovations);
+
+        let before = NeatNetwork::from_genome(&genome).propagate(vec![0.5, -0.25]);
+
+        assert!(genome.mutate_add_node(&mut rng, &mut innovations, Activation::Identity));
+
+        let after = NeatNetwork::from_genome(&genome).propagate(vec![0.5, -0.25]);
+
+        assert_relative_eq!(before.as_slice(), after.as_slice());
+    }
+
+    #[test]
+    fn add_connection_keeps_graph_acyclic() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut innovations = Innovations::new();
+        let mut genome = minimal(&mut rng, &mut innovations);
+
+        for _ in 0..5 {
+            genome.mutate_add_node(&mut rng, &mut innovations, Activation::Tanh);
+        }
+
+        while genome.mutate_add_connection(&mut rng, &mut innovations) {
+            //
+        }
+
+        assert!(genome.order().is_some());
+
+        // 8 nodes can be connected in at most 8 * 7 / 2 ways, minus the pair
+        // of inputs
+        assert_eq!(genome.connections().len(), 27);
+    }
+
+    #[test]
+    fn crossover() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+        let mut innovations = Innovations::new();
+
+        let other = minimal(&mut rng, &mut innovations);
+        let mut fitter = other.clone();
+
+        fitter.mutate_add_node(&mut rng, &mut innovations, Activation::Identity);
+
+        let child = NeatGenome::crossover(&mut rng, &fitter, &other);
+        let innovations = |genome: &NeatGenome| -> Vec<_> {
+            genome.connections().iter().map(|c| c.innovation).collect()
+        };
+
+        assert_eq!(innovations(&child), innovations(&fitter));
+        assert_eq!(child.nodes().len(), fitter.nodes().len());
+
+        let child = NeatGenome::crossover(&mut rng, &other, &fitter);
+
+        assert_eq!(innovations(&child), innovations(&other));
+    }
+
+    #[test]
+    fn distance() {
+        let connection = |innovation, weight| ConnectionGene {
+            innovation,
+            from: 0,
+            to: 1,
+            weight,
+            enabled: true,
+        };
+
+        let genome = |connections| NeatGenome {
+            nodes: Vec::new(),
+            connections,
+        };
+
+        let a = genome(vec![connection(0, 1.0), connection(2, 0.5), connection(3, 0.0)]);
+        let b = genome(vec![connection(0, 0.0), connection(1, 0.0)]);
+
+        let compatibility = Compatibility {
+            excess: 1.0,
+            disjoint: 2.0,
+            weight: 0.5,
+        };
+
+        // Innovation 1 is disjoint, 2 and 3 are excess, while 0 differs by
+        // 1.0 in weight
+        assert_relative_eq!(a.distance(&b, &compatibility), 2.0 / 3.0 + 2.0 / 3.0 + 0.5);
+        assert_relative_eq!(b.distance(&a, &compatibility), a.distance(&b, &compatibility));
+        assert_relative_eq!(a.distance(&a, &compatibility), 0.0);
+    }
+}
@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+
+/// Hands out innovation numbers (connection ids) and node ids, so that the
+/// same structural mutation gets the same numbers in every genome - which is
+/// what lets crossover line genomes up.
+#[derive(Clone, Debug, Default)]
+pub struct Innovations {
+    connections: HashMap<(usize, usize), usize>,
+    splits: HashMap<usize, usize>,
+    next_node: usize,
+}
+
+impl Innovations {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the innovation number of the connection between given nodes.
+    pub fn connection(&mut self, from: usize, to: usize) -> usize {
+        let next = self.connections.len();
+
+        *self.connections.entry((from, to)).or_insert(next)
+    }
+
+    /// Returns the id of the node that splits connection with given
+    /// innovation number.
+    pub fn split(&mut self, innovation: usize) -> usize {
+        if let Some(&node) = self.splits.get(&innovation) {
+            return node;
+        }
+
+        let node = self.node();
+
+        self.splits.insert(innovation, node);
+        node
+    }
+
+    /// Returns a node id that hasn't been handed out yet.
+    pub fn node(&mut self) -> usize {
+        self.next_node += 1;
+        self.next_node - 1
+    }
+
+    /// Makes sure ids below `count` never get handed out by `node()`; they
+    /// belong to input and output nodes.
+    pub(crate) fn reserve(&mut self, count: usize) {
+        self.next_node = self.next_node.max(count);
+    }
+}
@@ -0,0 +1,200 @@
+use crate::*;
+
+/// Phenotype of a `NeatGenome`: propagates inputs through the genome's
+/// enabled connections - which can form any directed acyclic graph - one
+/// node at a time, each node after all the nodes it's connected from.
+#[derive(Clone, Debug)]
+pub struct NeatNetwork {
+    /// Slot of each input node's value, in order of their ids.
+    inputs: Vec<usize>,
+
+    /// Slot of each output node's value, in order of their ids.
+    outputs: Vec<usize>,
+
+    /// Nodes to evaluate, input nodes excluded.
+    nodes: Vec<NeatNode>,
+
+    slots: usize,
+}
+
+#[derive(Clone, Debug)]
+struct NeatNode {
+    slot: usize,
+    bias: f32,
+    activation: Activation,
+
+    /// Slot of each incoming node's value, along with the weight.
+    links: Vec<(usize, f32)>,
+}
+
+impl NeatNetwork {
+    pub fn from_genome(genome: &NeatGenome) -> Self {
+        let order = genome.order().expect("got a cycle");
+        let mut links = vec![Vec::new(); genome.nodes().len()];
+
+        for connection in genome.connections().iter().filter(|c| c.enabled) {
+            links[genome.position(connection.to)]
+                .push((genome.position(connection.from), connection.weight));
+        }
+
+        let slots_of = |kind| {
+            (0..genome.nodes().len())
+                .filter(|&idx| genome.nodes()[idx].kind == kind)
+                .collect()
+        };
+
+        let nodes = order
+            .into_iter()
+            .filter(|&idx| genome.nodes()[idx].kind != NodeKind::Input)
+            .map(|idx| NeatNode {
+                slot: idx,
+                bias: genome.nodes()[idx].bias,
+                activation: genome.nodes()[idx].activation,
+                links: std::mem::take(&mut links[idx]),
+            })
+            .collect();
+
+        Self {
+            inputs: slots_of(NodeKind::Input),
+            outputs: slots_of(NodeKind::Output),
+            nodes,
+            slots: genome.nodes().len(),
+        }
+    }
+
+    pub fn propagate(&self, inputs: Vec<f32>) -> Vec<f32> {
+        self.propagate_into(&inputs, &mut Scratch::new()).to_vec()
+    }
+
+    /// Like `propagate()`, but works in given scratch, without allocating
+    /// once it's been used with a network of this size before.
+    pub fn propagate_into<'a>(&self, inputs: &[f32], scratch: &'a mut Scratch) -> &'a [f32] {
+        assert_eq!(inputs.len(), self.inputs.len());
+
+        let Scratch { values, buffer, .. } = &mut *scratch;
+
+        values.clear();
+        values.resize(self.slots, 0.0);
+
+        for (&slot, &input) in self.inputs.iter().zip(inputs) {
+            values[slot] = input;
+        }
+
+        for node in &self.nodes {
+            let sum: f32 = node
+                .links
+                .iter()
+                .map(|&(slot, weight)| values[slot] * weight)
+                .sum();
+
+            values[node.slot] = node.activation.apply(node.bias + sum);
+        }
+
+        buffer.clear();
+        buffer.extend(self.outputs.iter().map(|&slot| values[slot]));
+
+        &scratch.buffer
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_relative_eq;
+
+    fn node(id: usize, kind: NodeKind, bias: f32) -> NodeGene {
+        NodeGene {
+            id,
+            kind,
+            bias,
+            activation: Activation::Identity,
+        }
+    }
+
+    fn connection(innovation: usize, from: usize, to: usize, weight: f32) -> ConnectionGene {
+        ConnectionGene {
+            innovation,
+            from,
+            to,
+            weight,
+            enabled: true,
+        }
+    }
+
+    #[test]
+    fn propagate() {
+        // Hidden node 10 skips a layer: it's fed by both an input and
+        // hidden node 11, and feeds an output directly
+        let genome = NeatGenome::new(
+            vec![
+                node(0, NodeKind::Input, 0.0),
+                node(1, NodeKind::Input, 0.0),
+                node(2, NodeKind::Output, 0.5),
+                node(3, NodeKind::Output, 0.0),
+                node(10, NodeKind::Hidden, 0.0),
+                node(11, NodeKind::Hidden, 1.0),
+            ],
+            vec![
+                connection(0, 0, 11, 2.0),
+                connection(1, 11, 10, 0.5),
+                connection(2, 1, 10, -1.0),
+                connection(3, 10, 2, 3.0),
+                connection(4, 0, 3, 1.0),
+                ConnectionGene {
+                    enabled: false,
+                    ..connection(5, 1, 3, 100.0)
+                },
+            ],
+        );
+
+        let actual = NeatNetwork::from_genome(&genome).propagate(vec![1.0, 2.0]);
+
+        // node 11 = 1.0 + 2.0 * 1.0 = 3.0
+        // node 10 = 0.5 * 3.0 - 1.0 * 2.0 = -0.5
+        // node 2 = 0.5 + 3.0 * -0.5 = -1.0
+        // node 3 = 1.0 * 1.0 = 1.0
+        assert_relative_eq!(actual.as_slice(), [-1.0, 1.0].as_slice());
+    }
+
+    #[test]
+    fn propagate_into() {
+        let genome = NeatGenome::new(
+            vec![
+                node(0, NodeKind::Input, 0.0),
+                node(1, NodeKind::Output, 0.5),
+                node(2, NodeKind::Hidden, 0.0),
+            ],
+            vec![connection(0, 0, 2, 2.0), connection(1, 2, 1, -1.0)],
+        );
+
+        let network = NeatNetwork::from_genome(&genome);
+        let mut scratch = Scratch::new();
+
+        // The scratch gets reused, so stale values mustn't leak between calls
+        for input in [1.0, 3.0, 1.0] {
+            let actual = network.propagate_into(&[input], &mut scratch).to_vec();
+
+            assert_relative_eq!(actual.as_slice(), network.propagate(vec![input]).as_slice());
+            assert_relative_eq!(actual[0], 0.5 - 2.0 * input);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "got a cycle")]
+    fn rejects_cycles() {
+        NeatGenome::new(
+            vec![
+                node(0, NodeKind::Input, 0.0),
+                node(1, NodeKind::Output, 0.0),
+                node(2, NodeKind::Hidden, 0.0),
+                node(3, NodeKind::Hidden, 0.0),
+            ],
+            vec![
+                connection(0, 0, 2, 1.0),
+                connection(1, 2, 3, 1.0),
+                connection(2, 3, 2, 1.0),
+                connection(3, 3, 1, 1.0),
+            ],
+        );
+    }
+}
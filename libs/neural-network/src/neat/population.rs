@@ -0,0 +1,418 @@
+use crate::*;
+use std::cmp::Ordering;
+
+/// Evolves a population of `NeatGenome`s: the population gets clustered into
+/// species of structurally similar genomes (see `NeatGenome::distance()`),
+/// each species gets a number of offspring proportional to the average
+/// fitness of its members, and offspring are bred within species only - so
+/// that new structures get a few generations to tune their weights before
+/// having to compete with the rest of the population.
+///
+/// Innovation numbers and species are kept between generations, so a single
+/// instance should evolve the same population from start to end.
+#[derive(Clone, Debug)]
+pub struct Neat {
+    inputs: usize,
+    outputs: usize,
+    innovations: Innovations,
+    compatibility: Compatibility,
+    threshold: f32,
+    weight_chance: f32,
+    weight_coeff: f32,
+    add_node_chance: f32,
+    add_connection_chance: f32,
+    crossover_chance: f32,
+    survival: f32,
+    hidden_activation: Activation,
+    output_activation: Activation,
+    species: Vec<Species>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Species {
+    representative: NeatGenome,
+    size: usize,
+    best_fitness: f32,
+}
+
+impl Species {
+    /// Returns the genome new members get compared against - the fittest
+    /// member of the last generation.
+    pub fn representative(&self) -> &NeatGenome {
+        &self.representative
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn best_fitness(&self) -> f32 {
+        self.best_fitness
+    }
+}
+
+impl Neat {
+    pub fn new(inputs: usize, outputs: usize) -> Self {
+        assert!(inputs > 0);
+        assert!(outputs > 0);
+
+        Self {
+            inputs,
+            outputs,
+            innovations: Innovations::new(),
+            compatibility: Compatibility::default(),
+            threshold: 3.0,
+            weight_chance: 0.8,
+            weight_coeff: 0.5,
+            add_node_chance: 0.03,
+            add_connection_chance: 0.05,
+            crossover_chance: 0.75,
+            survival: 0.5,
+            hidden_activation: Activation::default(),
+            output_activation: Activation::default(),
+            species: Vec::new(),
+        }
+    }
+
+    /// Sets the distance (3.0 by default) below which two genomes belong to
+    /// the same species, along with how that distance gets computed.
+    pub fn with_compatibility(mut self, threshold: f32, compatibility: Compatibility) -> Self {
+        assert!(threshold > 0.0);
+
+        self.threshold = threshold;
+        self.compatibility = compatibility;
+        self
+    }
+
+    /// Sets how often (0.8 by default) and how much (0.5 by default) weights
+    /// and biases get nudged.
+    pub fn with_weight_mutation(mut self, chance: f32, coeff: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+        assert!(coeff >= 0.0);
+
+        self.weight_chance = chance;
+        self.weight_coeff = coeff;
+        self
+    }
+
+    /// Sets how often an offspring gets a new node (0.03 by default) and a
+    /// new connection (0.05 by default).
+    pub fn with_structural_mutation(mut self, add_node: f32, add_connection: f32) -> Self {
+        assert!((0.0..=1.0).contains(&add_node));
+        assert!((0.0..=1.0).contains(&add_connection));
+
+        self.add_node_chance = add_node;
+        self.add_connection_chance = add_connection;
+        self
+    }
+
+    /// Sets how often an offspring is bred from two parents rather than
+    /// cloned from one (0.75 by default).
+    pub fn with_crossover_chance(mut self, chance: f32) -> Self {
+        assert!((0.0..=1.0).contains(&chance));
+
+        self.crossover_chance = chance;
+        self
+    }
+
+    /// Sets the fraction of each species (0.5 by default), fittest first,
+    /// that gets to breed.
+    pub fn with_survival(mut self, survival: f32) -> Self {
+        assert!(survival > 0.0 && survival <= 1.0);
+
+        self.survival = survival;
+        self
+    }
+
+    pub fn with_activations(mut self, hidden: Activation, output: Activation) -> Self {
+        self.hidden_activation = hidden;
+        self.output_activation = output;
+        self
+    }
+
+    pub fn species(&self) -> &[Species] {
+        &self.species
+    }
+
+    /// Creates a genome for the initial population.
+    pub fn genome(&mut self, rng: &mut dyn RngCore) -> NeatGenome {
+        NeatGenome::minimal(
+            rng,
+            &mut self.innovations,
+            self.inputs,
+            self.outputs,
+            self.output_activation,
+        )
+    }
+
+    /// Speciates given genomes, each paired with its fitness, and breeds the
+    /// next generation out of them.
+    pub fn evolve(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[(NeatGenome, f32)],
+    ) -> Vec<NeatGenome> {
+        assert!(!population.is_empty());
+
+        let members = self.speciate(population);
+
+        let min_fitness = population
+            .iter()
+            .map(|(_, fitness)| *fitness)
+            .fold(f32::INFINITY, f32::min);
+
+        // Explicit fitness sharing: a species is worth its members' average
+        // fitness, so that no single species can take over the population
+        let shares: Vec<f32> = members
+            .iter()
+            .map(|members| {
+                let sum: f32 = members
+                    .iter()
+                    .map(|&idx| population[idx].1 - min_fitness)
+                    .sum();
+
+                sum / (members.len() as f32)
+            })
+            .collect();
+
+        let shares = if shares.iter().sum::<f32>() > 0.0 {
+            shares
+        } else {
+            members.iter().map(|members| members.len() as f32).collect()
+        };
+
+        let offspring = Self::allot(&shares, population.len());
+        let mut children = Vec::with_capacity(population.len());
+
+        for (members, count) in members.iter().zip(offspring) {
+            if count == 0 {
+                continue;
+            }
+
+            // The champion survives unchanged
+            children.push(population[members[0]].0.clone());
+
+            let survivors = ((members.len() as f32) * self.survival).ceil() as usize;
+            let parents = &members[..survivors.max(1)];
+
+            for _ in 1..count {
+                let child = self.breed(rng, population, parents);
+
+                children.push(child);
+            }
+        }
+
+        children
+    }
+
+    /// Assigns each genome to the first species whose representative lies
+    /// closer than the threshold, or founds a new species; returns the
+    /// members of each species, fittest first.
+    fn speciate(&mut self, population: &[(NeatGenome, f32)]) -> Vec<Vec<usize>> {
+        let mut members = vec![Vec::new(); self.species.len()];
+
+        for (idx, (genome, _)) in population.iter().enumerate() {
+            let species = self.species.iter().position(|species| {
+                species.representative.distance(genome, &self.compatibility) < self.threshold
+            });
+
+            match species {
+                Some(species) => members[species].push(idx),
+
+                None => {
+                    self.species.push(Species {
+                        representative: genome.clone(),
+                        size: 0,
+                        best_fitness: f32::NEG_INFINITY,
+                    });
+
+                    members.push(vec![idx]);
+                }
+            }
+        }
+
+        for members in &mut members {
+            members.sort_by(|&a, &b| {
+                population[b]
+                    .1
+                    .partial_cmp(&population[a].1)
+                    .unwrap_or(Ordering::Equal)
+            });
+        }
+
+        let (species, members): (Vec<_>, Vec<_>) = self
+            .species
+            .drain(..)
+            .zip(members)
+            .filter(|(_, members)| !members.is_empty())
+            .map(|(_, members)| {
+                let (champion, best_fitness) = &population[members[0]];
+
+                let species = Species {
+                    representative: champion.clone(),
+                    size: members.len(),
+                    best_fitness: *best_fitness,
+                };
+
+                (species, members)
+            })
+            .unzip();
+
+        self.species = species;
+        members
+    }
+
+    fn breed(
+        &mut self,
+        rng: &mut dyn RngCore,
+        population: &[(NeatGenome, f32)],
+        parents: &[usize],
+    ) -> NeatGenome {
+        let pos_a = rng.gen_range(0..parents.len());
+
+        let mut child = if parents.len() > 1 && rng.gen_bool(self.crossover_chance as _) {
+            // Pick a different parent, so that there's something to cross over
+            let pos_b = (pos_a + rng.gen_range(1..parents.len())) % parents.len();
+
+            // Parents are sorted fittest first, so the lower position belongs
+            // to the fitter one
+            let fitter = parents[pos_a.min(pos_b)];
+            let other = parents[pos_a.max(pos_b)];
+
+            NeatGenome::crossover(rng, &population[fitter].0, &population[other].0)
+        } else {
+            population[parents[pos_a]].0.clone()
+        };
+
+        child.mutate_weights(rng, self.weight_chance, self.weight_coeff);
+
+        if rng.gen_bool(self.add_node_chance as _) {
+            child.mutate_add_node(rng, &mut self.innovations, self.hidden_activation);
+        }
+
+        if rng.gen_bool(self.add_connection_chance as _) {
+            child.mutate_add_connection(rng, &mut self.innovations);
+        }
+
+        child
+    }
+
+    /// Splits `count` proportionally to given shares, handing out what's
+    /// left after rounding down to the largest remainders.
+    fn allot(shares: &[f32], count: usize) -> Vec<usize> {
+        let total: f32 = shares.iter().sum();
+
+        let exact: Vec<_> = shares
+            .iter()
+            .map(|share| share / total * (count as f32))
+            .collect();
+
+        let mut allotted: Vec<_> = exact.iter().map(|exact| *exact as usize).collect();
+        let mut remainders: Vec<_> = (0..shares.len()).collect();
+
+        remainders.sort_by(|&a, &b| {
+            let a = exact[a] - exact[a].floor();
+            let b = exact[b] - exact[b].floor();
+
+            b.partial_cmp(&a).unwrap_or(Ordering::Equal)
+        });
+
+        let left = count - allotted.iter().sum::<usize>();
+
+        for idx in remainders.into_iter().cycle().take(left) {
+            allotted[idx] += 1;
+        }
+
+        allotted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand_chacha::ChaCha8Rng;
+
+    /// Scores how well given genome computes `a or b`, at most 4.0.
+    fn fitness(genome: &NeatGenome) -> f32 {
+        let network = NeatNetwork::from_genome(genome);
+
+        [(0.0, 0.0, 0.0), (0.0, 1.0, 1.0), (1.0, 0.0, 1.0), (1.0, 1.0, 1.0)]
+            .iter()
+            .map(|&(a, b, expected)| 1.0 - (network.propagate(vec![a, b])[0] - expected).abs())
+            .sum()
+    }
+
+    fn best_fitness(population: &[NeatGenome]) -> f32 {
+        population
+            .iter()
+            .map(fitness)
+            .fold(f32::NEG_INFINITY, f32::max)
+    }
+
+    #[test]
+    fn evolve() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut neat = Neat::new(2, 1)
+            .with_structural_mutation(0.2, 0.3)
+            .with_activations(Activation::Tanh, Activation::Sigmoid);
+
+        let mut population: Vec<_> = (0..30).map(|_| neat.genome(&mut rng)).collect();
+        let initial_fitness = best_fitness(&population);
+
+        for _ in 0..30 {
+            let evaluated: Vec<_> = population
+                .into_iter()
+                .map(|genome| {
+                    let fitness = fitness(&genome);
+
+                    (genome, fitness)
+                })
+                .collect();
+
+            population = neat.evolve(&mut rng, &evaluated);
+
+            assert_eq!(population.len(), 30);
+        }
+
+        assert!(best_fitness(&population) > initial_fitness);
+        assert!(!neat.species().is_empty());
+        assert_eq!(neat.species().iter().map(|s| s.size()).sum::<usize>(), 30);
+
+        // Some genomes have grown hidden nodes
+        assert!(population.iter().any(|genome| genome.nodes().len() > 3));
+    }
+
+    #[test]
+    fn crossover_keeps_fitter_structure() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let mut neat = Neat::new(2, 1)
+            .with_weight_mutation(0.0, 0.0)
+            .with_structural_mutation(0.0, 0.0)
+            .with_crossover_chance(1.0);
+
+        let weaker = neat.genome(&mut rng);
+        let mut fitter = weaker.clone();
+
+        fitter.mutate_add_node(&mut rng, &mut neat.innovations, Activation::Identity);
+
+        // The fitter genome comes later in the population, but first among
+        // the parents
+        let population = [(weaker, 1.0), (fitter.clone(), 2.0)];
+
+        for _ in 0..10 {
+            let child = neat.breed(&mut rng, &population, &[1, 0]);
+
+            assert_eq!(child.nodes().len(), fitter.nodes().len());
+            assert_eq!(child.connections().len(), fitter.connections().len());
+        }
+    }
+
+    #[test]
+    fn allot() {
+        assert_eq!(Neat::allot(&[1.0, 1.0, 2.0], 10), vec![3, 2, 5]);
+        assert_eq!(Neat::allot(&[0.0, 3.0], 10), vec![0, 10]);
+    }
+}
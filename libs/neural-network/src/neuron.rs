@@ -31,22 +31,13 @@ impl Neuron {
 
         Self::new(bias, weights)
     }
-
-    pub fn propagate(&self, inputs: &[f32]) -> f32 {
-        assert_eq!(inputs.len(), self.weights.len());
-        let mut output = 0.0;
-        for (&input, &weight) in inputs.iter().zip(&self.weights) {
-            output += input * weight;
-        }
-        (self.bias + output).max(0.0)
-    }
 }
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use approx::{assert_relative_eq, assert_relative_ne};
+    use approx::assert_relative_eq;
     use rand::SeedableRng;
     use rand_chacha::ChaCha8Rng;
 
@@ -63,34 +54,6 @@ mod tests {
         );
     }
 
-    mod propagate {
-        use super::*;
-
-        #[test]
-        fn returns_propagated_input() {
-            let actual = Neuron::new(0.1, vec![-0.3, 0.6, 0.9])
-                .propagate(&[0.5, -0.6, 0.7]);
-            let expected: f32 = 0.1 + (0.5 * -0.3) + (-0.6 * 0.6) + (0.7 * 0.9);
-
-            approx::assert_relative_eq!(actual, expected.max(0.0));
-        }
-
-        #[test]
-        fn restricts_output() {
-            let neuron = Neuron::new(0.0, vec![0.5]);
-            let v1 = neuron.propagate(&[-1.0]);
-            let v2 = neuron.propagate(&[-0.5]);
-            let v3 = neuron.propagate(&[0.0]);
-            let v4 = neuron.propagate(&[0.5]);
-            let v5 = neuron.propagate(&[1.0]);
-
-            assert_relative_eq!(v1, v2);
-            assert_relative_eq!(v2, v3);
-            assert_relative_ne!(v3, v4);
-            assert_relative_ne!(v4, v5);
-        }
-    }
-
     #[test]
     fn from_weights() {
         let actual = Neuron::from_weights(3, &mut vec![0.1, 0.2, 0.3, 0.4].into_iter());
@@ -0,0 +1,54 @@
+use crate::*;
+
+/// Buffers `Network::propagate_into()` and `NeatNetwork::propagate_into()`
+/// work in, so that - once they've grown large enough - the forward pass
+/// doesn't allocate.
+///
+/// A scratch also holds the memory of recurrent layers, so each sequence of
+/// inputs (e.g. each animal) should get its own one.
+#[derive(Clone, Debug, Default)]
+pub struct Scratch {
+    pub(crate) values: Vec<f32>,
+    pub(crate) buffer: Vec<f32>,
+
+    /// Previous outputs of each layer; empty for non-recurrent ones.
+    pub(crate) states: Vec<Vec<f32>>,
+}
+
+impl Scratch {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clears the memory of recurrent layers.
+    pub fn reset_state(&mut self) {
+        for state in &mut self.states {
+            state.fill(0.0);
+        }
+    }
+
+    /// Makes sure there's a state of the right size for each layer, keeping
+    /// the existing ones if they already fit.
+    pub(crate) fn prepare(&mut self, layers: &[Layer]) {
+        let state_len = |layer: &Layer| {
+            if layer.recurrent {
+                layer.outputs()
+            } else {
+                0
+            }
+        };
+
+        let fits = self.states.len() == layers.len()
+            && layers
+                .iter()
+                .zip(&self.states)
+                .all(|(layer, state)| state.len() == state_len(layer));
+
+        if !fits {
+            self.states = layers
+                .iter()
+                .map(|layer| vec![0.0; state_len(layer)])
+                .collect();
+        }
+    }
+}
@@ -28,6 +28,8 @@ impl AnalyzeCmd {
                     entry_stats.max_fitness += log.stats.max_fitness;
                     entry_stats.avg_fitness += log.stats.avg_fitness;
                     entry_stats.median_fitness += log.stats.median_fitness;
+                    entry_stats.std_dev_fitness += log.stats.std_dev_fitness;
+                    entry_stats.diversity += log.stats.diversity;
                 }
 
                 btree_map::Entry::Vacant(entry) => {
@@ -48,6 +50,8 @@ impl AnalyzeCmd {
         print!(",max_fitness");
         print!(",avg_fitness");
         print!(",median_fitness");
+        print!(",std_dev_fitness");
+        print!(",diversity");
         println!();
 
         for (config, (samples, mut stats)) in results {
@@ -57,6 +61,8 @@ impl AnalyzeCmd {
             stats.max_fitness /= samples;
             stats.avg_fitness /= samples;
             stats.median_fitness /= samples;
+            stats.std_dev_fitness /= samples;
+            stats.diversity /= samples;
 
             print!("{}", config.brain_neurons);
             print!(",{}", config.eye_fov_range);
@@ -68,6 +74,8 @@ impl AnalyzeCmd {
             print!(",{}", stats.max_fitness);
             print!(",{}", stats.avg_fitness);
             print!(",{}", stats.median_fitness);
+            print!(",{}", stats.std_dev_fitness);
+            print!(",{}", stats.diversity);
             println!();
         }
     }
@@ -51,4 +51,10 @@ pub struct OptStatistics {
 
     #[serde(rename = "d")]
     pub median_fitness: f32,
+
+    #[serde(rename = "e", default)]
+    pub std_dev_fitness: f32,
+
+    #[serde(rename = "f", default)]
+    pub diversity: f32,
 }
\ No newline at end of file
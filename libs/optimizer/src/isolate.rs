@@ -0,0 +1,82 @@
+use lib_genetic_algorithm as ga;
+use lib_genetic_algorithm::Evaluator;
+use lib_simulation::{Config, Simulation};
+use structopt::StructOpt;
+
+/// Evolves birds whose fitness gets measured with each of them foraging
+/// alone, in a world of its own, across all the cores.
+#[derive(Debug, StructOpt)]
+pub struct IsolateCmd {
+    #[structopt(long, default_value = "30")]
+    generations: usize,
+}
+
+struct Bird {
+    chromosome: ga::Chromosome,
+    fitness: f32,
+}
+
+impl ga::Individual for Bird {
+    fn create(chromosome: ga::Chromosome) -> Self {
+        Self {
+            chromosome,
+            fitness: 0.0,
+        }
+    }
+
+    fn chromosome(&self) -> &ga::Chromosome {
+        &self.chromosome
+    }
+
+    fn fitness(&self) -> f32 {
+        self.fitness
+    }
+}
+
+impl IsolateCmd {
+    pub fn run(self) {
+        let config = Config::default();
+        let mut rng = rand::thread_rng();
+
+        let ga = ga::GeneticAlgorithm::new(
+            ga::RouletteWheelSelection,
+            ga::UniformCrossover,
+            ga::UniformMutation::new(config.ga_mut_chance, config.ga_mut_coeff),
+        );
+
+        let evaluator = ga::ParallelEvaluator::new(|bird: &mut Bird| {
+            bird.fitness = Simulation::fitness_in_isolation(
+                &config,
+                &mut rand::thread_rng(),
+                bird.chromosome.clone(),
+            );
+        });
+
+        let mut population: Vec<_> = Simulation::random(config.clone(), &mut rng)
+            .world()
+            .animals()
+            .iter()
+            .map(|animal| Bird {
+                chromosome: animal.as_chromosome(),
+                fitness: 0.0,
+            })
+            .collect();
+
+        evaluator.evaluate(&mut population);
+
+        for gen in 0..self.generations {
+            let (next_population, stats) =
+                ga.evolve_and_evaluate(&mut rng, &population, &evaluator);
+
+            println!(
+                "gen {} | min {:.2} | avg {:.2} | max {:.2}",
+                gen,
+                stats.min_fitness(),
+                stats.avg_fitness(),
+                stats.max_fitness(),
+            );
+
+            population = next_population;
+        }
+    }
+}
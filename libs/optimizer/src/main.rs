@@ -1,21 +1,25 @@
 mod analyze;
 mod common;
+mod isolate;
 mod simulate;
 
 use self::analyze::*;
 use self::common::*;
+use self::isolate::*;
 use self::simulate::*;
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 enum Cmd {
     Analyze(AnalyzeCmd),
+    Isolate(IsolateCmd),
     Simulate(SimulateCmd),
 }
 
 fn main() {
     match Cmd::from_args() {
         Cmd::Analyze(cmd) => cmd.run(),
+        Cmd::Isolate(cmd) => cmd.run(),
         Cmd::Simulate(cmd) => cmd.run(),
     }
 }
\ No newline at end of file
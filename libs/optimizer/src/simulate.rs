@@ -1,5 +1,6 @@
 use crate::{OptConfig, OptContext, OptLog, OptStatistics};
 use itertools::iproduct;
+use lib_genetic_algorithm as ga;
 use lib_simulation::{Config, Simulation};
 use ordered_float::OrderedFloat;
 use rand::seq::SliceRandom;
@@ -117,26 +118,39 @@ impl SimulateCmd {
                     };
 
                     let mut rng = rand::thread_rng();
-                    let mut sim = Simulation::random(config, &mut rng);
-
-                    for gen in 0..SIM_GENERATIONS {
-                        let stats = sim.train(&mut rng);
-
-                        log_tx
-                            .send(OptLog {
-                                cfg: opt_cfg,
-                                ctxt: OptContext { gen, iter },
-                                stats: OptStatistics {
-                                    min_fitness: stats.ga.min_fitness(),
-                                    max_fitness: stats.ga.max_fitness(),
-                                    avg_fitness: stats.ga.avg_fitness(),
-                                    median_fitness: stats.ga.median_fitness(),
-                                },
-                            })
-                            .unwrap();
-
-                        done_steps.fetch_add(1, Ordering::SeqCst);
-                    }
+
+                    let mut sim = match Simulation::try_random(config, &mut rng) {
+                        Ok(sim) => sim,
+
+                        Err(err) => {
+                            eprintln!("skipping {:?}: {}", opt_cfg, err);
+                            done_steps.fetch_add(SIM_GENERATIONS, Ordering::SeqCst);
+                            return;
+                        }
+                    };
+
+                    let mut runner = ga::Runner::new()
+                        .with_termination(ga::Termination::MaxGenerations(SIM_GENERATIONS))
+                        .with_observer(|gen, stats| {
+                            log_tx
+                                .send(OptLog {
+                                    cfg: opt_cfg,
+                                    ctxt: OptContext { gen, iter },
+                                    stats: OptStatistics {
+                                        min_fitness: stats.min_fitness(),
+                                        max_fitness: stats.max_fitness(),
+                                        avg_fitness: stats.avg_fitness(),
+                                        median_fitness: stats.median_fitness(),
+                                        std_dev_fitness: stats.std_dev_fitness(),
+                                        diversity: stats.diversity(),
+                                    },
+                                })
+                                .unwrap();
+
+                            done_steps.fetch_add(1, Ordering::SeqCst);
+                        });
+
+                    sim.run(&mut rng, &mut runner);
                 });
         });
 
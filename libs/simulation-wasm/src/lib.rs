@@ -34,10 +34,21 @@ impl Simulation {
         serde_wasm_bindgen::to_value(self.sim.config()).unwrap()
     }
 
+    /// Returns the first world; see `worlds()` when running with islands.
     pub fn world(&self) -> World {
         World::from(self.sim.world())
     }
 
+    /// Returns all the worlds, one per island.
+    pub fn worlds(&self) -> Vec<World> {
+        self.sim.worlds().iter().map(World::from).collect()
+    }
+
+    /// Returns the best brains seen so far, as JSON.
+    pub fn hall_of_fame(&self) -> String {
+        self.sim.hall_of_fame().to_json().unwrap()
+    }
+
     pub fn step(&mut self) -> Option<String> {
         self.sim.step(&mut self.rng).map(|stats| stats.to_string())
     }
@@ -10,6 +10,7 @@ pub struct Animal {
     pub(crate) eye: Eye,
     pub(crate) brain: Brain,
     pub(crate) satiation: usize,
+    pub(crate) distance_travelled: f32,
 }
 
 
@@ -44,14 +45,42 @@ impl Animal {
         Self::new(config, rng, brain)
     }
 
-    pub(crate) fn as_chromosome(&self) -> ga::Chromosome {
+    pub(crate) fn from_genome(
+        config: &Config,
+        rng: &mut dyn RngCore,
+        genome: nn::NeatGenome,
+    ) -> Self {
+        let brain = Brain::from_genome(config, genome);
+
+        Self::new(config, rng, brain)
+    }
+
+    /// Returns the weights of this animal's brain; empty for NEAT brains,
+    /// which are described by their genome instead.
+    pub fn as_chromosome(&self) -> ga::Chromosome {
         self.brain.as_chromosome()
     }
 
+    pub(crate) fn genome(&self) -> Option<&nn::NeatGenome> {
+        self.brain.genome()
+    }
+
+    /// Describes how this animal behaved: where it ended up and how much of
+    /// the longest possible path it has travelled.
+    pub(crate) fn behaviour(&self, config: &Config) -> Vec<f32> {
+        let max_distance = config.sim_speed_max * (config.sim_generation_length as f32);
+
+        vec![
+            self.position.x,
+            self.position.y,
+            self.distance_travelled / max_distance,
+        ]
+    }
+
     pub(crate) fn process_brain(&mut self, config: &Config, foods: &[Food]) {
         self.vision = self.eye.process_vision(self.position, self.rotation, foods);
 
-        let (speed, rotation) = self.brain.propagate(self.vision.clone());
+        let (speed, rotation) = self.brain.propagate(&self.vision);
 
         self.speed = (self.speed + speed).clamp(config.sim_speed_min, config.sim_speed_max);
         self.rotation = na::Rotation2::new(self.rotation.angle() + rotation);
@@ -59,6 +88,7 @@ impl Animal {
 
     pub(crate) fn process_movement(&mut self) {
         self.position += self.rotation * na::Vector2::new(0.0, self.speed);
+        self.distance_travelled += self.speed;
         self.position.x = na::wrap(self.position.x, 0.0, 1.0);
         self.position.y = na::wrap(self.position.y, 0.0, 1.0);
     }
@@ -74,6 +104,7 @@ impl Animal {
             eye: Eye::new(config),
             brain,
             satiation: 0,
+            distance_travelled: 0.0,
         }
     }
 }
\ No newline at end of file
@@ -1,8 +1,10 @@
 use crate::*;
 
+#[derive(Clone)]
 pub struct AnimalIndividual {
     pub(crate) fitness: f32,
     pub(crate) chromosome: ga::Chromosome,
+    pub(crate) behaviour: Vec<f32>,
 }
 
 impl ga::Individual for AnimalIndividual {
@@ -10,6 +12,7 @@ impl ga::Individual for AnimalIndividual {
         Self {
             fitness: 0.0,
             chromosome,
+            behaviour: Vec::new(),
         }
     }
     fn chromosome(&self) -> &ga::Chromosome {
@@ -18,13 +21,17 @@ impl ga::Individual for AnimalIndividual {
     fn fitness(&self) -> f32 {
         self.fitness
     }
+    fn behaviour(&self) -> Vec<f32> {
+        self.behaviour.clone()
+    }
 }
 
 impl AnimalIndividual {
-    pub fn from_animal(animal: &Animal) -> Self {
+    pub fn from_animal(config: &Config, animal: &Animal) -> Self {
         Self {
             fitness: animal.satiation as f32,
             chromosome: animal.as_chromosome(),
+            behaviour: animal.behaviour(config),
         }
     }
     pub fn into_animal(self, config: &Config, rng: &mut dyn RngCore) -> Animal {
@@ -1,17 +1,21 @@
 use crate::*;
+use std::ops::RangeInclusive;
 
 #[derive(Debug)]
 pub struct Brain {
     speed_accel: f32,
     rotation_accel: f32,
-    nn: nn::Network,
+    nn: BrainNetwork,
+    scratch: nn::Scratch,
+    output_bounds: Option<RangeInclusive<f32>>,
+    step_sizes: Vec<f32>,
 }
 
 impl Brain {
     pub(crate) fn random(config: &Config, rng: &mut dyn RngCore) -> Self {
         let nn = nn::Network::random(rng, &Self::topology(config));
 
-        Self::new(config, nn)
+        Self::new(config, BrainNetwork::Dense(nn))
     }
 
     pub(crate) fn from_chromosome(config: &Config, chromosome: ga::Chromosome) -> Self {
@@ -21,43 +25,111 @@ impl Brain {
 
         let nn = nn::Network::from_weights(&Self::topology(config), chromosome.iter());
 
-        Self::new(config, nn)
+        Self {
+            step_sizes: chromosome.step_sizes().to_vec(),
+            ..Self::new(config, BrainNetwork::Dense(nn))
+        }
     }
 
+    pub(crate) fn from_genome(config: &Config, genome: nn::NeatGenome) -> Self {
+        let nn = nn::NeatNetwork::from_genome(&genome);
+
+        Self::new(config, BrainNetwork::Neat(genome, nn))
+    }
+
+    /// Returns the brain's weights; brains grown by NEAT don't fit into a
+    /// fixed-length chromosome, so theirs is empty (see `genome()`).
     pub(crate) fn as_chromosome(&self) -> ga::Chromosome {
-        self.nn.weights().into_iter().collect()
+        match &self.nn {
+            BrainNetwork::Dense(nn) => nn
+                .weights()
+                .into_iter()
+                .collect::<ga::Chromosome>()
+                .with_step_sizes(self.step_sizes.clone()),
+
+            BrainNetwork::Neat(..) => std::iter::empty().collect(),
+        }
     }
 
-    pub(crate) fn propagate(&self, vision: Vec<f32>) -> (f32, f32) {
-        let response = self.nn.propagate(vision);
+    /// Returns the genome this brain has been grown from, if it's been
+    /// evolved by NEAT.
+    pub(crate) fn genome(&self) -> Option<&nn::NeatGenome> {
+        match &self.nn {
+            BrainNetwork::Dense(_) => None,
+            BrainNetwork::Neat(genome, _) => Some(genome),
+        }
+    }
+
+    pub(crate) fn propagate(&mut self, vision: &[f32]) -> (f32, f32) {
+        let (r0, r1) = match &self.nn {
+            BrainNetwork::Dense(nn) => {
+                let response = nn.propagate_into(vision, &mut self.scratch);
+
+                (response[0], response[1])
+            }
+
+            BrainNetwork::Neat(_, nn) => {
+                let response = nn.propagate_into(vision, &mut self.scratch);
+
+                (response[0], response[1])
+            }
+        };
 
-        let r0 = response[0].clamp(0.0, 1.0) - 0.5;
-        let r1 = response[1].clamp(0.0, 1.0) - 0.5;
+        let r0 = self.normalize(r0) - 0.5;
+        let r1 = self.normalize(r1) - 0.5;
         let speed = (r0 + r1).clamp(-self.speed_accel, self.speed_accel);
         let rotation = (r0 - r1).clamp(-self.rotation_accel, self.rotation_accel);
 
         (speed, rotation)
     }
+
+    /// Returns how many genes of the chromosome belong to each neuron.
+    pub(crate) fn neuron_layout(config: &Config) -> Vec<usize> {
+        nn::Network::neuron_layout(&Self::topology(config))
+    }
 }
 
 impl Brain {
-    fn new(config: &Config, nn: nn::Network) -> Self {
+    fn new(config: &Config, nn: BrainNetwork) -> Self {
         Self {
             speed_accel: config.sim_speed_accel,
             rotation_accel: config.sim_rotation_accel,
             nn,
+            scratch: nn::Scratch::new(),
+            output_bounds: config.brain_output_activation.to_nn().bounds(),
+            step_sizes: Vec::new(),
+        }
+    }
+
+    /// Maps an output of the network into `0.0..=1.0` - by rescaling it, if
+    /// the output activation is bounded (e.g. tanh), or by clamping it.
+    fn normalize(&self, output: f32) -> f32 {
+        match &self.output_bounds {
+            Some(bounds) => (output - bounds.start()) / (bounds.end() - bounds.start()),
+            None => output.clamp(0.0, 1.0),
         }
     }
 
     fn topology(config: &Config) -> [nn::LayerTopology; 3] {
+        let mut hidden = nn::LayerTopology::new(config.brain_neurons)
+            .with_activation(config.brain_hidden_activation.to_nn());
+
+        if config.brain_recurrent {
+            hidden = hidden.with_recurrence();
+        }
+
         [
-            nn::LayerTopology {
-                neurons: config.eye_cells,
-            },
-            nn::LayerTopology {
-                neurons: config.brain_neurons,
-            },
-            nn::LayerTopology { neurons: 2 },
+            nn::LayerTopology::new(config.eye_cells),
+            hidden,
+            nn::LayerTopology::new(2).with_activation(config.brain_output_activation.to_nn()),
         ]
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug)]
+enum BrainNetwork {
+    Dense(nn::Network),
+
+    /// Network grown by NEAT, along with the genome it's been grown from.
+    Neat(nn::NeatGenome, nn::NeatNetwork),
+}
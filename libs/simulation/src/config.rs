@@ -1,9 +1,12 @@
 use crate::*;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
-#[serde(deny_unknown_fields)]
+#[serde(default, deny_unknown_fields)]
 pub struct Config {
     pub brain_neurons: usize,
+    pub brain_hidden_activation: ActivationKind,
+    pub brain_output_activation: ActivationKind,
+    pub brain_recurrent: bool,
 
     pub eye_fov_range: f32,
     pub eye_fov_angle: f32,
@@ -11,9 +14,48 @@ pub struct Config {
 
     pub food_size: f32,
 
+    pub ga_strategy: StrategyKind,
     pub ga_reverse: usize,
     pub ga_mut_chance: f32,
     pub ga_mut_coeff: f32,
+    pub ga_mutation: MutationKind,
+    pub ga_mut_adaptation: AdaptationKind,
+    pub ga_mut_patience: usize,
+    pub ga_mut_diversity: f32,
+    pub ga_selection: SelectionKind,
+    pub ga_tournament_size: usize,
+    pub ga_tournament_probability: f32,
+    pub ga_rank_pressure: f32,
+    pub ga_rank_base: f32,
+    pub ga_elite_count: usize,
+    pub ga_survivors: SurvivorKind,
+    pub ga_survivors_mu: usize,
+    pub ga_speciation_threshold: f32,
+    pub ga_percentiles: Vec<f32>,
+    pub ga_novelty_weight: f32,
+    pub ga_novelty_k: usize,
+    pub ga_crossover: CrossoverKind,
+    pub ga_crossover_points: usize,
+    pub ga_crossover_alpha: f32,
+    pub ga_crossover_eta: f32,
+    pub ga_gene_bound: f32,
+    pub ga_repair: RepairKind,
+    pub ga_hall_of_fame: usize,
+    pub ga_champions: usize,
+    pub ga_islands: usize,
+    pub ga_migration: MigrationKind,
+    pub ga_migration_interval: usize,
+    pub ga_migrants: usize,
+    pub ga_cma_sigma: f32,
+    /// Each restart doubles the number of samples per generation, so every
+    /// world can grow up to `2^ga_cma_max_restarts * world_animals` animals.
+    pub ga_cma_max_restarts: usize,
+    pub ga_de_variant: DifferentialKind,
+    pub ga_de_f: f32,
+    pub ga_de_cr: f32,
+    pub ga_neat_threshold: f32,
+    pub ga_neat_add_node: f32,
+    pub ga_neat_add_connection: f32,
 
     pub sim_speed_min: f32,
     pub sim_speed_max: f32,
@@ -29,6 +71,9 @@ impl Default for Config {
     fn default() -> Self {
         Self {
             brain_neurons: 9,
+            brain_hidden_activation: ActivationKind::Relu,
+            brain_output_activation: ActivationKind::Relu,
+            brain_recurrent: false,
             //
             eye_fov_range: 0.25,
             eye_fov_angle: PI + FRAC_PI_4,
@@ -36,9 +81,46 @@ impl Default for Config {
             //
             food_size: 0.01,
             //
+            ga_strategy: StrategyKind::GeneticAlgorithm,
             ga_reverse: 0,
             ga_mut_chance: 0.01,
             ga_mut_coeff: 0.3,
+            ga_mutation: MutationKind::Uniform,
+            ga_mut_adaptation: AdaptationKind::None,
+            ga_mut_patience: 10,
+            ga_mut_diversity: 0.1,
+            ga_selection: SelectionKind::RouletteWheel,
+            ga_tournament_size: 3,
+            ga_tournament_probability: 0.9,
+            ga_rank_pressure: 1.5,
+            ga_rank_base: 0.95,
+            ga_elite_count: 0,
+            ga_survivors: SurvivorKind::Generational,
+            ga_survivors_mu: 10,
+            ga_speciation_threshold: 0.0,
+            ga_percentiles: vec![10.0, 90.0],
+            ga_novelty_weight: 0.0,
+            ga_novelty_k: 15,
+            ga_crossover: CrossoverKind::Uniform,
+            ga_crossover_points: 2,
+            ga_crossover_alpha: 0.5,
+            ga_crossover_eta: 2.0,
+            ga_gene_bound: 0.0,
+            ga_repair: RepairKind::Clamp,
+            ga_hall_of_fame: 10,
+            ga_champions: 0,
+            ga_islands: 1,
+            ga_migration: MigrationKind::Ring,
+            ga_migration_interval: 10,
+            ga_migrants: 2,
+            ga_cma_sigma: 0.5,
+            ga_cma_max_restarts: 4,
+            ga_de_variant: DifferentialKind::Rand1Bin,
+            ga_de_f: 0.5,
+            ga_de_cr: 0.9,
+            ga_neat_threshold: 3.0,
+            ga_neat_add_node: 0.03,
+            ga_neat_add_connection: 0.05,
             //
             sim_speed_min: 0.001,
             sim_speed_max: 0.005,
@@ -50,4 +132,275 @@ impl Default for Config {
             world_foods: 60,
         }
     }
-}
\ No newline at end of file
+}
+
+impl Config {
+    /// Checks the genetic algorithm's parameters, so that a bad configuration
+    /// can be reported instead of making the simulation panic later.
+    pub fn validate(&self) -> Result<(), ga::GaError> {
+        if self.world_animals == 0 {
+            return Err(ga::GaError::EmptyPopulation);
+        }
+
+        ga::GaError::check_positive("islands", self.ga_islands as f32)?;
+        ga::GaError::check_positive("migration interval", self.ga_migration_interval as f32)?;
+        ga::GaError::check_percentiles(&self.ga_percentiles)?;
+
+        if self.ga_survivors == SurvivorKind::MuPlusLambda {
+            ga::GaError::check_positive("mu", self.ga_survivors_mu as f32)?;
+        }
+
+        if self.ga_novelty_weight > 0.0 {
+            ga::NoveltySearch::try_new(self.ga_novelty_k, self.ga_novelty_weight)?;
+        }
+
+        match self.ga_selection {
+            SelectionKind::RouletteWheel | SelectionKind::StochasticUniversalSampling => (),
+
+            SelectionKind::Tournament => {
+                ga::TournamentSelection::try_new(
+                    self.ga_tournament_size,
+                    self.ga_tournament_probability,
+                )?;
+            }
+
+            SelectionKind::LinearRank => {
+                ga::RankSelection::try_new(ga::RankPressure::Linear(self.ga_rank_pressure))?;
+            }
+
+            SelectionKind::ExponentialRank => {
+                ga::RankSelection::try_new(ga::RankPressure::Exponential(self.ga_rank_base))?;
+            }
+        }
+
+        match self.ga_crossover {
+            CrossoverKind::Uniform | CrossoverKind::SinglePoint | CrossoverKind::Neuron => (),
+
+            CrossoverKind::KPoint => {
+                ga::KPointCrossover::try_new(self.ga_crossover_points)?;
+            }
+
+            CrossoverKind::Arithmetic => {
+                ga::ArithmeticCrossover::try_new(self.ga_crossover_alpha)?;
+            }
+
+            CrossoverKind::Blend => {
+                ga::BlendCrossover::try_new(self.ga_crossover_alpha)?;
+            }
+
+            CrossoverKind::SimulatedBinary => {
+                ga::SimulatedBinaryCrossover::try_new(self.ga_crossover_eta)?;
+            }
+        }
+
+        let (chance, coeff) = (self.ga_mut_chance, self.ga_mut_coeff);
+
+        match self.ga_mutation {
+            MutationKind::Uniform => {
+                ga::UniformMutation::try_new(chance, coeff)?;
+            }
+
+            MutationKind::Gaussian => {
+                ga::GaussianMutation::try_new(chance, coeff)?;
+            }
+
+            MutationKind::SelfAdaptive | MutationKind::SelfAdaptivePerGene => {
+                ga::SelfAdaptiveMutation::try_new(chance, coeff)?;
+            }
+        }
+
+        // Other strategies breed their offspring from their own state, which
+        // champions reinserted into the population would bypass
+        ga::GaError::check(
+            self.ga_champions == 0 || self.ga_strategy == StrategyKind::GeneticAlgorithm,
+            "champions",
+            self.ga_champions as f32,
+            "0 unless using the genetic algorithm",
+        )?;
+
+        match self.ga_strategy {
+            StrategyKind::GeneticAlgorithm | StrategyKind::Neat => (),
+
+            StrategyKind::CmaEs => {
+                ga::GaError::check(
+                    self.world_animals >= 2,
+                    "animals",
+                    self.world_animals as f32,
+                    "at least 2 for CMA-ES",
+                )?;
+
+                ga::CmaEs::try_new(self.ga_cma_sigma)?;
+            }
+
+            StrategyKind::DifferentialEvolution => {
+                ga::GaError::check(
+                    self.world_animals >= 4,
+                    "animals",
+                    self.world_animals as f32,
+                    "at least 4 for differential evolution",
+                )?;
+
+                ga::DifferentialEvolution::try_new(
+                    ga::DifferentialVariant::Rand1Bin,
+                    self.ga_de_f,
+                    self.ga_de_cr,
+                )?;
+            }
+        }
+
+        if self.ga_strategy == StrategyKind::Neat {
+            // NEAT genomes don't get encoded into chromosomes, which is what
+            // novelty search works on
+            ga::GaError::check(
+                self.ga_novelty_weight == 0.0,
+                "novelty weight",
+                self.ga_novelty_weight,
+                "0.0 with NEAT",
+            )?;
+
+            ga::GaError::check_positive("neat threshold", self.ga_neat_threshold)?;
+            ga::GaError::check_probability("neat add-node chance", self.ga_neat_add_node)?;
+
+            ga::GaError::check_probability(
+                "neat add-connection chance",
+                self.ga_neat_add_connection,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActivationKind {
+    Relu,
+    LeakyRelu,
+    Sigmoid,
+    Tanh,
+    Identity,
+    Softsign,
+    Step,
+}
+
+impl ActivationKind {
+    pub(crate) fn to_nn(self) -> nn::Activation {
+        match self {
+            Self::Relu => nn::Activation::Relu,
+            Self::LeakyRelu => nn::Activation::LeakyRelu(0.01),
+            Self::Sigmoid => nn::Activation::Sigmoid,
+            Self::Tanh => nn::Activation::Tanh,
+            Self::Identity => nn::Activation::Identity,
+            Self::Softsign => nn::Activation::Softsign,
+            Self::Step => nn::Activation::Step,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StrategyKind {
+    GeneticAlgorithm,
+    CmaEs,
+    DifferentialEvolution,
+    Neat,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SelectionKind {
+    RouletteWheel,
+    Tournament,
+    LinearRank,
+    ExponentialRank,
+    StochasticUniversalSampling,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SurvivorKind {
+    Generational,
+    MuPlusLambda,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossoverKind {
+    Uniform,
+    SinglePoint,
+    KPoint,
+    Arithmetic,
+    Blend,
+    SimulatedBinary,
+    Neuron,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MutationKind {
+    Uniform,
+    Gaussian,
+    SelfAdaptive,
+    SelfAdaptivePerGene,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdaptationKind {
+    None,
+    OneFifth,
+    Stagnation,
+    Diversity,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RepairKind {
+    Clamp,
+    Reflect,
+    Wrap,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationKind {
+    Ring,
+    Random,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DifferentialKind {
+    Rand1Bin,
+    Best1Bin,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_fields_default() {
+        let config: Config =
+            serde_json::from_str(r#"{ "world_animals": 10, "ga_strategy": "cma_es" }"#).unwrap();
+
+        assert_eq!(config.world_animals, 10);
+        assert_eq!(config.ga_strategy, StrategyKind::CmaEs);
+        assert_eq!(config.world_foods, Config::default().world_foods);
+    }
+
+    #[test]
+    fn neat_with_defaults() {
+        let config = Config {
+            ga_strategy: StrategyKind::Neat,
+            ..Default::default()
+        };
+
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        assert!(serde_json::from_str::<Config>(r#"{ "world_animal": 10 }"#).is_err());
+    }
+}
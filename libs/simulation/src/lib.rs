@@ -22,18 +22,144 @@ use {lib_genetic_algorithm as ga, lib_neural_network as nn, nalgebra as na};
 
 pub struct Simulation {
     config: Config,
-    world: World,
+    worlds: Vec<World>,
+    novelty: Option<ga::NoveltySearch>,
+    mutation: Option<ga::AdaptiveMutation>,
+    hall_of_fame: ga::HallOfFame,
+    cma_es: Vec<ga::CmaEs>,
+    differential_evolution: Vec<ga::DifferentialEvolution>,
+    neat: Vec<nn::Neat>,
     age: usize,
     generation: usize,
 }
 
 impl Simulation {
+    /// Like `random()`, but checks the configuration first (see
+    /// `Config::validate()`).
+    pub fn try_random(config: Config, rng: &mut dyn RngCore) -> Result<Self, ga::GaError> {
+        config.validate()?;
+
+        Ok(Self::random(config, rng))
+    }
+
     pub fn random(config: Config, rng: &mut dyn RngCore) -> Self {
-        let world = World::random(&config, rng);
+        assert!(config.ga_islands > 0);
+
+        let mut worlds: Vec<_> = (0..config.ga_islands)
+            .map(|_| World::random(&config, rng))
+            .collect();
+
+        let novelty = (config.ga_novelty_weight > 0.0)
+            .then(|| ga::NoveltySearch::new(config.ga_novelty_k, config.ga_novelty_weight));
+
+        let adaptation_rule = match config.ga_mut_adaptation {
+            AdaptationKind::None => None,
+            AdaptationKind::OneFifth => Some(ga::AdaptationRule::OneFifth),
+            AdaptationKind::Stagnation => Some(ga::AdaptationRule::Stagnation {
+                patience: config.ga_mut_patience,
+            }),
+            AdaptationKind::Diversity => Some(ga::AdaptationRule::Diversity {
+                target: config.ga_mut_diversity,
+            }),
+        };
+
+        let mutation = adaptation_rule.map(|rule| {
+            let kind = config.ga_mutation;
+
+            ga::AdaptiveMutation::new(
+                rule,
+                config.ga_mut_chance,
+                config.ga_mut_coeff,
+                move |chance, coeff| Self::mutation_method(kind, chance, coeff),
+            )
+        });
+
+        // NEAT animals have no chromosomes to remember
+        let hall_of_fame = match config.ga_strategy {
+            StrategyKind::Neat => ga::HallOfFame::new(0),
+            _ => ga::HallOfFame::new(config.ga_hall_of_fame),
+        };
+
+        let cma_es = match config.ga_strategy {
+            StrategyKind::CmaEs => (0..config.ga_islands)
+                .map(|_| {
+                    let cma_es = ga::CmaEs::new(config.ga_cma_sigma)
+                        .with_max_restarts(config.ga_cma_max_restarts);
+
+                    match Self::bounds(&config) {
+                        Some(bounds) => cma_es.with_constraint(bounds),
+                        None => cma_es,
+                    }
+                })
+                .collect(),
+
+            _ => Vec::new(),
+        };
+
+        let differential_evolution = match config.ga_strategy {
+            StrategyKind::DifferentialEvolution => {
+                let variant = match config.ga_de_variant {
+                    DifferentialKind::Rand1Bin => ga::DifferentialVariant::Rand1Bin,
+                    DifferentialKind::Best1Bin => ga::DifferentialVariant::Best1Bin,
+                };
+
+                (0..config.ga_islands)
+                    .map(|_| {
+                        let de = ga::DifferentialEvolution::new(
+                            variant,
+                            config.ga_de_f,
+                            config.ga_de_cr,
+                        );
+
+                        match Self::bounds(&config) {
+                            Some(bounds) => de.with_constraint(bounds),
+                            None => de,
+                        }
+                    })
+                    .collect()
+            }
+
+            _ => Vec::new(),
+        };
+
+        let mut neat = match config.ga_strategy {
+            StrategyKind::Neat => (0..config.ga_islands)
+                .map(|_| {
+                    nn::Neat::new(config.eye_cells, 2)
+                        .with_compatibility(config.ga_neat_threshold, Default::default())
+                        .with_weight_mutation(config.ga_mut_chance, config.ga_mut_coeff)
+                        .with_structural_mutation(
+                            config.ga_neat_add_node,
+                            config.ga_neat_add_connection,
+                        )
+                        .with_activations(
+                            config.brain_hidden_activation.to_nn(),
+                            config.brain_output_activation.to_nn(),
+                        )
+                })
+                .collect(),
+
+            _ => Vec::new(),
+        };
+
+        // NEAT starts from minimal networks instead of random dense ones
+        for (world, neat) in worlds.iter_mut().zip(&mut neat) {
+            for animal in &mut world.animals {
+                let genome = neat.genome(rng);
+
+                *animal = Animal::from_genome(&config, rng, genome);
+            }
+        }
 
         Self {
             config,
-            world,
+            worlds,
+            novelty,
+            mutation,
+            hall_of_fame,
+            cma_es,
+            differential_evolution,
+            neat,
             age: 0,
             generation: 0,
         }
@@ -43,14 +169,27 @@ impl Simulation {
         &self.config
     }
 
+    /// Returns the first world; see `worlds()` when running with islands.
     pub fn world(&self) -> &World {
-        &self.world
+        &self.worlds[0]
+    }
+
+    /// Returns all the worlds, one per island, each of which evolves its own
+    /// population.
+    pub fn worlds(&self) -> &[World] {
+        &self.worlds
+    }
+
+    /// Returns the best brains seen so far, across all the worlds.
+    pub fn hall_of_fame(&self) -> &ga::HallOfFame {
+        &self.hall_of_fame
     }
 
     pub fn step(&mut self, rng: &mut dyn RngCore) -> Option<Statistics> {
-        self.process_collisions(rng);
-        self.process_brains();
-        self.process_movements();
+        for world in &mut self.worlds {
+            world.step(&self.config, rng);
+        }
+
         self.try_evolving(rng)
     }
 
@@ -61,34 +200,37 @@ impl Simulation {
             }
         }
     }
-}
 
-impl Simulation {
-    fn process_collisions(&mut self, rng: &mut dyn RngCore) {
-        for animal in &mut self.world.animals {
-            for food in &mut self.world.foods {
-                let distance = na::distance(&animal.position, &food.position);
-
-                if distance <= self.config.food_size {
-                    animal.satiation += 1;
-                    food.position = rng.gen();
-                }
-            }
-        }
+    /// Trains generation after generation until one of the runner's
+    /// termination conditions is met.
+    pub fn run(&mut self, rng: &mut dyn RngCore, runner: &mut ga::Runner) -> ga::RunOutcome {
+        runner.run(|| self.train(rng).ga)
     }
 
-    fn process_brains(&mut self) {
-        for animal in &mut self.world.animals {
-            animal.process_brain(&self.config, &self.world.foods);
-        }
-    }
+    /// Scores given chromosome (see `Animal::as_chromosome()`) by letting its
+    /// animal forage alone, in a world of its own, for a whole generation -
+    /// so that a population can be evaluated in parallel, e.g. with
+    /// `ga::ParallelEvaluator`.
+    pub fn fitness_in_isolation(
+        config: &Config,
+        rng: &mut dyn RngCore,
+        chromosome: ga::Chromosome,
+    ) -> f32 {
+        let mut world = World {
+            animals: vec![Animal::from_chromosome(config, rng, chromosome)],
+            foods: (0..config.world_foods).map(|_| Food::random(rng)).collect(),
+        };
 
-    fn process_movements(&mut self) {
-        for animal in &mut self.world.animals {
-            animal.process_movement();
+        // Same number of steps as `step()` takes per generation
+        for _ in 0..=config.sim_generation_length {
+            world.step(config, rng);
         }
+
+        world.animals[0].satiation as f32
     }
+}
 
+impl Simulation {
     fn try_evolving(&mut self, rng: &mut dyn RngCore) -> Option<Statistics> {
         self.age += 1;
 
@@ -103,16 +245,91 @@ impl Simulation {
         self.age = 0;
         self.generation += 1;
 
-        let mut individuals: Vec<_> = self
-            .world
+        let islands: Vec<_> = self
+            .worlds
+            .iter()
+            .map(|world| self.individuals(world))
+            .collect();
+
+        if self.config.ga_strategy == StrategyKind::Neat {
+            return self.evolve_with_neat(rng, &islands);
+        }
+
+        for island in &islands {
+            self.hall_of_fame.record(self.generation - 1, island);
+        }
+
+        let (islands, statistics) = match self.config.ga_strategy {
+            StrategyKind::GeneticAlgorithm => match self.config.ga_selection {
+                SelectionKind::RouletteWheel => {
+                    self.evolve_with(rng, ga::RouletteWheelSelection, &islands)
+                }
+
+                SelectionKind::Tournament => self.evolve_with(
+                    rng,
+                    ga::TournamentSelection::new(
+                        self.config.ga_tournament_size,
+                        self.config.ga_tournament_probability,
+                    ),
+                    &islands,
+                ),
+
+                SelectionKind::LinearRank => self.evolve_with(
+                    rng,
+                    ga::RankSelection::linear(self.config.ga_rank_pressure),
+                    &islands,
+                ),
+
+                SelectionKind::ExponentialRank => self.evolve_with(
+                    rng,
+                    ga::RankSelection::exponential(self.config.ga_rank_base),
+                    &islands,
+                ),
+
+                SelectionKind::StochasticUniversalSampling => {
+                    self.evolve_with(rng, ga::StochasticUniversalSampling, &islands)
+                }
+            },
+
+            StrategyKind::CmaEs => self.evolve_with_cma_es(rng, &islands),
+
+            StrategyKind::DifferentialEvolution => {
+                self.evolve_with_differential_evolution(rng, &islands)
+            }
+
+            StrategyKind::Neat => unreachable!(),
+        };
+
+        for (world, mut individuals) in self.worlds.iter_mut().zip(islands) {
+            if self.config.ga_strategy == StrategyKind::GeneticAlgorithm {
+                self.hall_of_fame.reinsert(&mut individuals, self.config.ga_champions);
+            }
+
+            world.animals = individuals
+                .into_iter()
+                .map(|i| i.into_animal(&self.config, rng))
+                .collect();
+
+            for food in &mut world.foods {
+                food.position = rng.gen();
+            }
+        }
+
+        Statistics {
+            generation: self.generation - 1,
+            ga: statistics,
+        }
+    }
+
+    fn individuals(&self, world: &World) -> Vec<AnimalIndividual> {
+        let mut individuals: Vec<_> = world
             .animals
             .iter()
-            .map(AnimalIndividual::from_animal)
+            .map(|animal| AnimalIndividual::from_animal(&self.config, animal))
             .collect();
 
         if self.config.ga_reverse == 1 {
-            let max_satiation = self
-                .world
+            let max_satiation = world
                 .animals
                 .iter()
                 .map(|animal| animal.satiation)
@@ -124,26 +341,230 @@ impl Simulation {
             }
         }
 
-        let ga = ga::GeneticAlgorithm::new(
-            ga::RouletteWheelSelection,
-            ga::UniformCrossover,
-            ga::GaussianMutation::new(self.config.ga_mut_chance, self.config.ga_mut_coeff),
+        individuals
+    }
+
+    fn evolve_with<S>(
+        &mut self,
+        rng: &mut dyn RngCore,
+        selection_method: S,
+        islands: &[Vec<AnimalIndividual>],
+    ) -> (Vec<Vec<AnimalIndividual>>, ga::Statistics)
+        where
+            S: ga::SelectionMethod,
+    {
+        let survivor_strategy = match self.config.ga_survivors {
+            SurvivorKind::Generational => ga::SurvivorStrategy::Generational,
+            SurvivorKind::MuPlusLambda => ga::SurvivorStrategy::MuPlusLambda {
+                mu: self.config.ga_survivors_mu,
+            },
+        };
+
+        let crossover_method: Box<dyn ga::CrossoverMethod> = match self.config.ga_crossover {
+            CrossoverKind::Uniform => Box::new(ga::UniformCrossover),
+            CrossoverKind::SinglePoint => Box::new(ga::SinglePointCrossover),
+            CrossoverKind::KPoint => {
+                Box::new(ga::KPointCrossover::new(self.config.ga_crossover_points))
+            }
+            CrossoverKind::Arithmetic => {
+                Box::new(ga::ArithmeticCrossover::new(self.config.ga_crossover_alpha))
+            }
+            CrossoverKind::Blend => {
+                Box::new(ga::BlendCrossover::new(self.config.ga_crossover_alpha))
+            }
+            CrossoverKind::SimulatedBinary => {
+                Box::new(ga::SimulatedBinaryCrossover::new(self.config.ga_crossover_eta))
+            }
+            CrossoverKind::Neuron => {
+                Box::new(ga::SegmentCrossover::new(Brain::neuron_layout(&self.config)))
+            }
+        };
+
+        let mutation_method = match &mut self.mutation {
+            Some(mutation) => {
+                mutation.adapt(islands.iter().flatten());
+                Box::new(mutation.clone())
+            }
+
+            None => Self::mutation_method(
+                self.config.ga_mutation,
+                self.config.ga_mut_chance,
+                self.config.ga_mut_coeff,
+            ),
+        };
+
+        let mut ga = ga::GeneticAlgorithm::new(selection_method, crossover_method, mutation_method)
+            .with_elitism(self.config.ga_elite_count)
+            .with_survivor_strategy(survivor_strategy)
+            .with_percentiles(self.config.ga_percentiles.clone());
+
+        if self.config.ga_speciation_threshold > 0.0 {
+            ga = ga.with_speciation(self.config.ga_speciation_threshold);
+        }
+
+        if let Some(bounds) = Self::bounds(&self.config) {
+            ga = ga.with_constraint(bounds);
+        }
+
+        let migration_topology = match self.config.ga_migration {
+            MigrationKind::Ring => ga::MigrationTopology::Ring,
+            MigrationKind::Random => ga::MigrationTopology::Random,
+        };
+
+        let island_model = ga::IslandModel::new(ga).with_migration(
+            self.config.ga_migration_interval,
+            self.config.ga_migrants,
+            migration_topology,
         );
 
-        let (individuals, statistics) = ga.evolve(rng, &individuals);
+        match &mut self.novelty {
+            Some(novelty) => {
+                island_model.evolve_with_novelty(rng, self.generation, islands, novelty)
+            }
+            None => island_model.evolve(rng, self.generation, islands),
+        }
+    }
+
+    /// Returns bounds for brain weights, unless they are disabled.
+    fn bounds(config: &Config) -> Option<ga::Bounds> {
+        if config.ga_gene_bound <= 0.0 {
+            return None;
+        }
+
+        let repair = match config.ga_repair {
+            RepairKind::Clamp => ga::Repair::Clamp,
+            RepairKind::Reflect => ga::Repair::Reflect,
+            RepairKind::Wrap => ga::Repair::Wrap,
+        };
+
+        Some(ga::Bounds::uniform(
+            -config.ga_gene_bound..=config.ga_gene_bound,
+            repair,
+        ))
+    }
 
-        self.world.animals = individuals
-            .into_iter()
-            .map(|i| i.into_animal(&self.config, rng))
+    fn mutation_method(
+        kind: MutationKind,
+        chance: f32,
+        coeff: f32,
+    ) -> Box<dyn ga::MutationMethod + Send + Sync> {
+        match kind {
+            MutationKind::Uniform => Box::new(ga::UniformMutation::new(chance, coeff)),
+            MutationKind::Gaussian => Box::new(ga::GaussianMutation::new(chance, coeff)),
+            MutationKind::SelfAdaptive => Box::new(ga::SelfAdaptiveMutation::new(chance, coeff)),
+            MutationKind::SelfAdaptivePerGene => {
+                Box::new(ga::SelfAdaptiveMutation::per_gene(chance, coeff))
+            }
+        }
+    }
+
+    /// Evolves each island with its own CMA-ES instance; islands don't
+    /// exchange individuals in this mode.
+    fn evolve_with_cma_es(
+        &mut self,
+        rng: &mut dyn RngCore,
+        islands: &[Vec<AnimalIndividual>],
+    ) -> (Vec<Vec<AnimalIndividual>>, ga::Statistics) {
+        let statistics = ga::Statistics::new(islands.iter().flatten(), &self.config.ga_percentiles);
+
+        let islands = self
+            .cma_es
+            .iter_mut()
+            .zip(islands)
+            .map(|(cma_es, island)| cma_es.evolve(rng, island))
             .collect();
 
-        for food in &mut self.world.foods {
-            food.position = rng.gen();
+        (islands, statistics)
+    }
+
+    /// Evolves each island with its own differential evolution instance;
+    /// islands don't exchange individuals in this mode.
+    fn evolve_with_differential_evolution(
+        &mut self,
+        rng: &mut dyn RngCore,
+        islands: &[Vec<AnimalIndividual>],
+    ) -> (Vec<Vec<AnimalIndividual>>, ga::Statistics) {
+        let statistics = ga::Statistics::new(islands.iter().flatten(), &self.config.ga_percentiles);
+
+        let islands = self
+            .differential_evolution
+            .iter_mut()
+            .zip(islands)
+            .map(|(de, island)| de.evolve(rng, island).0)
+            .collect();
+
+        (islands, statistics)
+    }
+
+    /// Evolves each island with its own NEAT instance; islands don't exchange
+    /// individuals in this mode and - since genomes don't fit into
+    /// chromosomes - the hall of fame doesn't get updated.
+    fn evolve_with_neat(
+        &mut self,
+        rng: &mut dyn RngCore,
+        islands: &[Vec<AnimalIndividual>],
+    ) -> Statistics {
+        let statistics = ga::Statistics::new(islands.iter().flatten(), &self.config.ga_percentiles);
+
+        // Chromosomes of NEAT animals are empty, so their diversity has to
+        // be measured on genomes instead
+        let diversity = Self::genome_diversity(&self.worlds);
+
+        for ((world, neat), island) in self.worlds.iter_mut().zip(&mut self.neat).zip(islands) {
+            let population: Vec<_> = world
+                .animals
+                .iter()
+                .zip(island)
+                .map(|(animal, individual)| {
+                    let genome = animal.genome().expect("got an animal without a genome");
+
+                    (genome.clone(), individual.fitness)
+                })
+                .collect();
+
+            world.animals = neat
+                .evolve(rng, &population)
+                .into_iter()
+                .map(|genome| Animal::from_genome(&self.config, rng, genome))
+                .collect();
+
+            for food in &mut world.foods {
+                food.position = rng.gen();
+            }
         }
 
+        let species = self.neat.iter().map(|neat| neat.species().len()).sum();
+
         Statistics {
             generation: self.generation - 1,
-            ga: statistics,
+            ga: statistics.with_species(species).with_diversity(diversity),
+        }
+    }
+
+    /// Returns the mean distance between every two genomes (see
+    /// `nn::NeatGenome::distance()`).
+    fn genome_diversity(worlds: &[World]) -> f32 {
+        let genomes: Vec<_> = worlds
+            .iter()
+            .flat_map(|world| &world.animals)
+            .map(|animal| animal.genome().expect("got an animal without a genome"))
+            .collect();
+
+        let compatibility = nn::Compatibility::default();
+        let mut sum = 0.0;
+        let mut pairs = 0;
+
+        for (idx, a) in genomes.iter().enumerate() {
+            for b in &genomes[idx + 1..] {
+                sum += a.distance(b, &compatibility);
+                pairs += 1;
+            }
+        }
+
+        if pairs == 0 {
+            0.0
+        } else {
+            sum / (pairs as f32)
         }
     }
 }
@@ -167,4 +588,31 @@ mod tests {
 
         approx::assert_relative_eq!(31.944998, avg_fitness);
     }
+
+    #[test]
+    fn cma_es() {
+        let mut rng = ChaCha8Rng::from_seed(Default::default());
+
+        let config = Config {
+            ga_strategy: StrategyKind::CmaEs,
+            ga_cma_max_restarts: 2,
+            brain_neurons: 1,
+            eye_cells: 1,
+            sim_generation_length: 20,
+            world_animals: 8,
+            ..Default::default()
+        };
+
+        let mut sim = Simulation::try_random(config, &mut rng).unwrap();
+        let mut sizes = Vec::new();
+
+        for _ in 0..100 {
+            sim.train(&mut rng);
+            sizes.push(sim.world().animals().len());
+        }
+
+        // Restarts double the population, at most `ga_cma_max_restarts` times
+        assert!(sizes.iter().all(|size| [8, 16, 32].contains(size)));
+        assert_eq!(sizes.last(), Some(&32));
+    }
 }
\ No newline at end of file
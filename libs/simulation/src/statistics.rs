@@ -17,6 +17,27 @@ impl fmt::Display for Statistics {
             self.ga.max_fitness(),
             self.ga.avg_fitness(),
             self.ga.median_fitness()
-        )
+        )?;
+
+        write!(
+            f,
+            " std-dev[{:.2}] diversity[{:.2}]",
+            self.ga.std_dev_fitness(),
+            self.ga.diversity()
+        )?;
+
+        for (percentile, fitness) in self.ga.percentiles() {
+            write!(f, " p{}[{:.2}]", percentile, fitness)?;
+        }
+
+        if let Some(species) = self.ga.species() {
+            write!(f, " species[{}]", species)?;
+        }
+
+        if let Some(rates) = self.ga.mutation_rates() {
+            write!(f, " mut-chance[{:.4}] mut-coeff[{:.4}]", rates.chance, rates.coeff)?;
+        }
+
+        Ok(())
     }
 }
\ No newline at end of file
@@ -24,4 +24,35 @@ impl World {
 
         Self { animals, foods }
     }
+
+    pub(crate) fn step(&mut self, config: &Config, rng: &mut dyn RngCore) {
+        self.process_collisions(config, rng);
+        self.process_brains(config);
+        self.process_movements();
+    }
+
+    fn process_collisions(&mut self, config: &Config, rng: &mut dyn RngCore) {
+        for animal in &mut self.animals {
+            for food in &mut self.foods {
+                let distance = na::distance(&animal.position, &food.position);
+
+                if distance <= config.food_size {
+                    animal.satiation += 1;
+                    food.position = rng.gen();
+                }
+            }
+        }
+    }
+
+    fn process_brains(&mut self, config: &Config) {
+        for animal in &mut self.animals {
+            animal.process_brain(config, &self.foods);
+        }
+    }
+
+    fn process_movements(&mut self) {
+        for animal in &mut self.animals {
+            animal.process_movement();
+        }
+    }
 }
\ No newline at end of file
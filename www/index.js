@@ -109,7 +109,7 @@ printTerminal([
     "",
     "- The `reset` command can modify all parameters:",
     "",
-    "  * r i:integer_param=123 f:float_param=123",
+    "  * r i:integer_param=123 f:float_param=123 s:string_param=abc",
     "  * r a=200 f=200 f:food_size=0.002",
     "",
     "  (Note: Parameter names can be found in the source code)",
@@ -128,6 +128,9 @@ printTerminal([
     "  * r f:eye_fov_angle=0.45",
     "    (Narrow field of view)",
     "",
+    "  * r s:ga_selection=tournament i:ga_tournament_size=5",
+    "    (Tournament selection instead of roulette wheel)",
+    "",
     "----"
 ]);
 
@@ -144,7 +147,8 @@ const COMMANDS = {
 
 const CONFIG_PARSERS = {
     i: parseInt,
-    f: parseFloat
+    f: parseFloat,
+    s: String
 };
 
 const CONFIG_ALIASES = {